@@ -0,0 +1,22 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::element::Element;
+use crate::tensor::Tensor;
+
+pub mod trace;
+
+/// 计算图的执行器
+///
+/// 先用 `input` 绑定变量的值, 再 `compute` 需要的张量, 最后用 `fetch` 取出结果.
+pub trait Backend {
+    type Element: Element;
+    type Error: Debug;
+
+    fn input(&mut self, tensor: &Tensor, data: Arc<Vec<Self::Element>>);
+
+    fn compute(&mut self, tensor: &Tensor) -> Result<(), Self::Error>;
+
+    /// 取出张量的值, 未计算过时会先计算
+    fn fetch(&mut self, tensor: &Tensor) -> Result<Arc<Vec<Self::Element>>, Self::Error>;
+}
@@ -0,0 +1,174 @@
+use std::any::TypeId;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::backend::Backend;
+use crate::cpu::{CpuContext, CpuError, CpuKernel, CpuResult};
+use crate::element::Element;
+use crate::tensor::{Tensor, TensorHandle};
+
+/// 一次算子计算的记录
+#[derive(Debug, Clone)]
+pub struct TraceRecord {
+    pub operator: String,
+    pub shape: Vec<usize>,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Default)]
+struct TraceState {
+    records: Vec<TraceRecord>,
+    /// 正在计算的各个节点中, 其参数已经花费的时间
+    stack: Vec<Duration>,
+}
+
+/// 以 `CpuContext` 计算, 并记录每个算子自身的耗时
+///
+/// 计算前把图中每类算子的实现换成计时的包装, 计算顺序与 `CpuContext` 完全相同:
+/// 未选中的 `Select` 分支不会计算, 相同种子下随机算子的结果也相同.
+/// 通过 `context_mut` 设置的实现要在计算前设置, 否则不会计时.
+#[derive(Debug)]
+pub struct TraceBackend<T: Element = f32> {
+    context: CpuContext<T>,
+    state: Arc<Mutex<TraceState>>,
+    /// 已经包装的算子类型
+    wrapped: HashSet<TypeId>,
+}
+
+impl<T: Element> Default for TraceBackend<T> {
+    fn default() -> Self {
+        Self {
+            context: CpuContext::default(),
+            state: Arc::default(),
+            wrapped: HashSet::new(),
+        }
+    }
+}
+
+impl TraceBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T: Element> TraceBackend<T> {
+    /// 按计算完成的顺序排列, 每个节点只出现一次
+    pub fn records(&self) -> Vec<TraceRecord> {
+        self.state.lock().unwrap().records.clone()
+    }
+
+    pub fn total(&self) -> Duration {
+        self.records().iter().map(|r| r.elapsed).sum()
+    }
+
+    pub fn context(&self) -> &CpuContext<T> {
+        &self.context
+    }
+
+    /// 用于设置种子和训练模式
+    pub fn context_mut(&mut self) -> &mut CpuContext<T> {
+        &mut self.context
+    }
+
+    /// 为 `tensor` 依赖的尚未计算的节点包装计时
+    fn wrap(&mut self, tensor: &Tensor) {
+        let mut stack = vec![tensor.clone()];
+        let mut visited = HashSet::<TensorHandle>::new();
+        while let Some(tensor) = stack.pop() {
+            if self.context.get(&tensor).is_some() || !visited.insert(tensor.clone().into()) {
+                continue;
+            }
+            stack.extend(tensor.arguments().iter().cloned());
+            let operator = tensor.operator();
+            if !self.wrapped.insert(operator.type_id()) {
+                continue;
+            }
+            // 没有实现时保留 `CpuContext` 的报错
+            let Some(kernel) = self.context.kernel(operator) else {
+                continue;
+            };
+            let state = self.state.clone();
+            let timed = CpuKernel::new(move |tensor, context| {
+                state.lock().unwrap().stack.push(Duration::ZERO);
+                let start = Instant::now();
+                let result = kernel.compute(tensor, context);
+                let elapsed = start.elapsed();
+                // 只记录自身的耗时, 不含计算参数的时间
+                let mut state = state.lock().unwrap();
+                let children = state.stack.pop().unwrap();
+                if let Some(parent) = state.stack.last_mut() {
+                    *parent += elapsed;
+                }
+                if result.is_ok() {
+                    state.records.push(TraceRecord {
+                        operator: format!("{:?}", tensor.operator()),
+                        shape: tensor.shape().to_vec(),
+                        elapsed: elapsed.saturating_sub(children),
+                    });
+                }
+                result
+            });
+            self.context.set_kernel_of(operator, timed);
+        }
+    }
+}
+
+impl<T: Element> Backend for TraceBackend<T> {
+    type Element = T;
+    type Error = CpuError;
+
+    fn input(&mut self, tensor: &Tensor, data: Arc<Vec<T>>) {
+        self.context.input(tensor, data);
+    }
+
+    fn compute(&mut self, tensor: &Tensor) -> Result<(), CpuError> {
+        self.fetch(tensor).map(|_| ())
+    }
+
+    fn fetch(&mut self, tensor: &Tensor) -> CpuResult<T> {
+        self.wrap(tensor);
+        self.context.compute(tensor)
+    }
+}
+
+#[test]
+fn test() {
+    let x = Tensor::variable([2]);
+    let y = &x * &x + &x;
+    let mut backend = TraceBackend::new();
+    backend.input(&x, Arc::new(vec![1.0, 2.0]));
+    assert_eq!(y.compute_on(&mut backend).unwrap().as_slice(), [2.0, 6.0]);
+    let operators = backend
+        .records()
+        .into_iter()
+        .map(|r| r.operator)
+        .collect::<Vec<_>>();
+    assert_eq!(operators, ["MulTensor", "AddTensor"]);
+
+    // 已计算的节点不再记录
+    y.compute_on(&mut backend).unwrap();
+    assert_eq!(backend.records().len(), 2);
+}
+
+#[test]
+fn lazy_select() {
+    let x = Tensor::constant([2], Arc::new(vec![1.0, 2.0]));
+    let bad = x.index_select(0, Tensor::indices([1], Arc::new(vec![5])));
+    let noise = Tensor::normal([1], 0.0, 1.0, None);
+    let y = Tensor::scale(1.0).select(Tensor::normal([1], 0.0, 1.0, None), bad + noise);
+
+    let mut backend = TraceBackend::new();
+    backend.context_mut().set_seed(3);
+    let traced = y.compute_on(&mut backend).unwrap();
+    // 未选中的分支既不计算也不消耗随机数
+    assert_eq!(backend.records().len(), 3);
+    assert!(backend
+        .records()
+        .iter()
+        .all(|r| !r.operator.starts_with("IndexSelect")));
+
+    let mut context = CpuContext::new();
+    context.set_seed(3);
+    assert_eq!(context.compute(&y).unwrap(), traced);
+}
@@ -76,3 +76,18 @@ fn test() {
     let ref b = a + a;
     assert_eq!(b.back(a).compute().unwrap().as_slice(), [2.0]);
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([2, 2], Arc::new(vec![1.0, -2.0, 3.0, 0.5]));
+    let b = Tensor::constant([2, 2], Arc::new(vec![0.1, 0.2, -0.3, 0.4]));
+    let s = Tensor::constant([1], Arc::new(vec![2.0]));
+    let check = gradcheck(
+        |x| AddTensor::add(vec![x[0].clone(), x[1].clone(), x[0].clone()]) + &x[2],
+        &[a, b, s],
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
@@ -0,0 +1,125 @@
+use crate::core::TensorOperator;
+use crate::dtype::DType;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 沿第 `axis` 维排序后前 `k` 个元素的下标, 结果为 `DType::I64`
+///
+/// 排序是稳定的, 相等的元素按原顺序排列, 因此 argmax 取第一个最大值.
+/// 按 `Element::total_cmp` 比较, 正的 NaN 大于任何数.
+#[derive(Debug, Copy, Clone)]
+pub struct ArgSort {
+    axis: usize,
+    k: usize,
+    descending: bool,
+}
+
+impl ArgSort {
+    pub fn tensor(input: Tensor, axis: usize, k: usize, descending: bool) -> Tensor {
+        assert!(axis < input.shape().len());
+        assert!(k <= input.shape()[axis]);
+        let mut shape = input.shape().to_vec();
+        shape[axis] = k;
+        Tensor::new_with_dtype(
+            shape,
+            vec![input],
+            Box::new(ArgSort {
+                axis,
+                k,
+                descending,
+            }),
+            DType::I64,
+        )
+    }
+
+    pub fn axis(&self) -> usize {
+        self.axis
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn descending(&self) -> bool {
+        self.descending
+    }
+}
+
+impl TensorOperator for ArgSort {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, _context: &mut ForwardGrad) -> Tensor {
+        Tensor::zero(tensor.shape())
+    }
+
+    fn backward_grad(&self, _tensor: &Tensor, _grad: &Tensor, _context: &mut BackwardGrad) {}
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    #[rustfmt::skip]
+    let x = Tensor::constant([2, 4], Arc::new(vec![
+        3.0, 1.0, 3.0, 2.0,
+        0.0, 5.0, -1.0, 5.0,
+    ]));
+    let max = x.argmax(1);
+    assert_eq!(max.shape(), [2]);
+    assert_eq!(max.dtype(), DType::I64);
+    assert_eq!(max.compute().unwrap().as_slice(), [0.0, 1.0]);
+    assert_eq!(x.argmin(1).compute().unwrap().as_slice(), [1.0, 2.0]);
+    assert_eq!(
+        x.argmax(0).compute().unwrap().as_slice(),
+        [0.0, 1.0, 0.0, 1.0]
+    );
+
+    let (values, indices) = x.topk(2, 1);
+    assert_eq!(values.shape(), [2, 2]);
+    assert_eq!(values.compute().unwrap().as_slice(), [3.0, 3.0, 5.0, 5.0]);
+    assert_eq!(indices.compute().unwrap().as_slice(), [0.0, 2.0, 1.0, 3.0]);
+
+    let (values, indices) = x.sort(1);
+    #[rustfmt::skip]
+    assert_eq!(values.compute().unwrap().as_slice(), [
+        1.0, 2.0, 3.0, 3.0,
+        -1.0, 0.0, 5.0, 5.0,
+    ]);
+    #[rustfmt::skip]
+    assert_eq!(indices.compute().unwrap().as_slice(), [
+        1.0, 3.0, 0.0, 2.0,
+        2.0, 0.0, 1.0, 3.0,
+    ]);
+
+    let nan = Tensor::constant([4], Arc::new(vec![1.0, f32::NAN, 3.0, -2.0]));
+    let (_, indices) = nan.sort(0);
+    assert_eq!(indices.compute().unwrap().as_slice(), [3.0, 0.0, 2.0, 1.0]);
+    assert_eq!(nan.argmax(0).compute().unwrap().as_slice(), [1.0]);
+    assert_eq!(nan.argmin(0).compute().unwrap().as_slice(), [3.0]);
+
+    // 梯度经选中的下标传回
+    let (values, _) = x.topk(1, 1);
+    assert_eq!(
+        values.back(&x).compute().unwrap().as_slice(),
+        [1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0]
+    );
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 3], Arc::new(vec![1.0, -2.0, 3.0, 0.5, 1.5, -1.0]));
+    let check = gradcheck(
+        |x| {
+            let (values, _) = x[0].sort(0);
+            let (top, _) = values.topk(2, 1);
+            &top * &top
+        },
+        std::slice::from_ref(&x),
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
@@ -33,3 +33,13 @@ impl TensorOperator for Assign {
         Display::fmt(warp, f)
     }
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([3], Arc::new(vec![1.0, -2.0, 0.5]));
+    let check = gradcheck(|x| x[0].assign() * &x[0], &[a]);
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
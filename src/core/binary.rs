@@ -0,0 +1,144 @@
+use std::fmt::{Display, Formatter};
+
+use crate::core::function::Function;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 逐元素二元函数 f(a, b)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Binary {
+    /// max(a, b), 相等时梯度全部给 a
+    Maximum,
+    /// min(a, b), 相等时梯度全部给 a
+    Minimum,
+    /// a^b, 关于 b 的导数 a^b ln(a) 要求 a > 0
+    Pow,
+    /// atan2(a, b), 即点 (b, a) 的辐角, 在原点不可导
+    Atan2,
+    /// sqrt(a^2 + b^2), 在原点不可导
+    Hypot,
+}
+
+impl Binary {
+    pub fn apply(self, a: Tensor, b: Tensor) -> Tensor {
+        assert_eq!(a.shape(), b.shape());
+        Tensor::new(a.shape().to_vec(), vec![a, b], Box::new(self))
+    }
+
+    /// 关于 a 和 b 的偏导数
+    fn partial(self, tensor: &Tensor) -> (Tensor, Tensor) {
+        let [a, b] = tensor.arguments() else { panic!() };
+        match self {
+            Binary::Maximum => (a.ge(b), a.lt(b)),
+            Binary::Minimum => (a.le(b), a.gt(b)),
+            Binary::Pow => (
+                Binary::Pow.apply(a.clone(), b - 1.0) * b,
+                tensor * a.apply(Function::Ln),
+            ),
+            Binary::Atan2 => {
+                let r = (a * a + b * b).powf(-1.0);
+                (b * &r, -a * r)
+            }
+            Binary::Hypot => {
+                let r = tensor.powf(-1.0);
+                (a * &r, b * r)
+            }
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Binary::Maximum => "maximum",
+            Binary::Minimum => "minimum",
+            Binary::Pow => "pow",
+            Binary::Atan2 => "atan2",
+            Binary::Hypot => "hypot",
+        }
+    }
+}
+
+impl TensorOperator for Binary {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [a, b] = tensor.arguments() else { panic!() };
+        let (da, db) = self.partial(tensor);
+        context.compute(a) * da + context.compute(b) * db
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [a, b] = tensor.arguments() else { panic!() };
+        let (da, db) = self.partial(tensor);
+        context.append(a, grad * da);
+        context.append(b, grad * db);
+    }
+
+    fn display(&self, tensor: &Tensor, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let [a, b] = tensor.arguments() else { panic!() };
+        f.write_str(self.name())?;
+        f.write_str("(")?;
+        Display::fmt(a, f)?;
+        f.write_str(", ")?;
+        Display::fmt(b, f)?;
+        f.write_str(")")?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let a = Tensor::constant([3], Arc::new(vec![1.0, 2.0, 3.0]));
+    let b = Tensor::constant([3], Arc::new(vec![3.0, 2.0, 1.0]));
+    assert_eq!(a.maximum(&b).compute().unwrap().as_slice(), [3.0, 2.0, 3.0]);
+    assert_eq!(a.minimum(&b).compute().unwrap().as_slice(), [1.0, 2.0, 1.0]);
+    assert_eq!(a.pow(&b).compute().unwrap().as_slice(), [1.0, 4.0, 3.0]);
+    assert_eq!(
+        a.maximum(Tensor::scale(2.5)).compute().unwrap().as_slice(),
+        [2.5, 2.5, 3.0]
+    );
+
+    // 只有一个元素的非标量与标量
+    let one = Tensor::constant([1], Arc::new(vec![1.0]));
+    let y = one.maximum(Tensor::scale(2.0));
+    assert_eq!(y.shape(), [1]);
+    assert_eq!(y.compute().unwrap().as_slice(), [2.0]);
+    let y = Tensor::scale(2.0).minimum(&one);
+    assert_eq!(y.shape(), [1]);
+    assert_eq!(y.compute().unwrap().as_slice(), [1.0]);
+
+    // 相等时梯度给 a
+    let y = a.maximum(&b);
+    assert_eq!(y.back(&a).compute().unwrap().as_slice(), [0.0, 1.0, 1.0]);
+    assert_eq!(y.back(&b).compute().unwrap().as_slice(), [1.0, 0.0, 0.0]);
+    let y = a.minimum(&b);
+    assert_eq!(y.back(&a).compute().unwrap().as_slice(), [1.0, 1.0, 0.0]);
+    assert_eq!(y.back(&b).compute().unwrap().as_slice(), [0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    // 避开相等和原点
+    let a = Tensor::constant([3], Arc::new(vec![0.7, 1.3, 2.1]));
+    let b = Tensor::constant([3], Arc::new(vec![-1.2, 1.9, 0.4]));
+    for op in [
+        Binary::Maximum,
+        Binary::Minimum,
+        Binary::Pow,
+        Binary::Atan2,
+        Binary::Hypot,
+    ] {
+        let check = gradcheck(
+            |x| op.apply(x[0].clone(), x[1].clone()),
+            &[a.clone(), b.clone()],
+        );
+        assert!(check.max_error() < 1e-2, "{:?} {:?}", op, check);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::core::TensorOperator;
+use crate::dtype::DType;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 转换数据类型, 转为整数时向零取整, 转为布尔时非零为 1
+///
+/// 只有浮点之间的转换传递梯度.
+#[derive(Debug, Copy, Clone)]
+pub struct Cast {
+    dtype: DType,
+}
+
+impl Cast {
+    pub fn cast(tensor: Tensor, dtype: DType) -> Tensor {
+        Tensor::new_with_dtype(
+            tensor.shape().to_vec(),
+            vec![tensor],
+            Box::new(Cast { dtype }),
+            dtype,
+        )
+    }
+
+    pub fn dtype(&self) -> DType {
+        self.dtype
+    }
+}
+
+impl TensorOperator for Cast {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [arg] = tensor.arguments() else { panic!() };
+        context.compute(arg)
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [arg] = tensor.arguments() else { panic!() };
+        context.append(arg, grad);
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let x = Tensor::constant([4], Arc::new(vec![-1.5, 0.0, 0.7, 2.0]));
+    assert_eq!(x.dtype(), DType::F32);
+    let i = x.cast(DType::I32);
+    assert_eq!(i.dtype(), DType::I32);
+    assert_eq!(i.compute().unwrap().as_slice(), [-1.0, 0.0, 0.0, 2.0]);
+    assert_eq!(
+        x.cast(DType::Bool).compute().unwrap().as_slice(),
+        [1.0, 0.0, 1.0, 1.0]
+    );
+
+    // 整数参与运算时结果取较宽的类型
+    assert_eq!((&i + &x).dtype(), DType::F32);
+    assert_eq!((&i * &i).dtype(), DType::I32);
+
+    // 整数不传递梯度
+    let v = Tensor::variable([4]);
+    let y = &v * v.cast(DType::I64) + &v * v.cast(DType::F64);
+    assert_eq!(y.dtype(), DType::F64);
+    let value = Arc::new(vec![-1.5, 0.0, 0.7, 2.0]);
+    let grad = y.back(&v).compute_with([(&v, value)]).unwrap();
+    assert_eq!(grad.as_slice(), [-4.0, 0.0, 1.4, 6.0]);
+}
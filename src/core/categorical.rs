@@ -0,0 +1,82 @@
+use crate::core::TensorOperator;
+use crate::dtype::DType;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 以最后一维为各类的非负权重 (不必归一化) 抽取类别序号, 结果为 `DType::I64`
+///
+/// 输出形状为去掉最后一维的形状, 种子的含义同 `Random`. 某一行的权重有负数或 NaN,
+/// 或总和不是正的有限数时, 计算返回 `CpuError::InvalidWeights`.
+#[derive(Debug, Copy, Clone)]
+pub struct Categorical {
+    seed: Option<u64>,
+}
+
+impl Categorical {
+    pub fn tensor(weights: Tensor, seed: Option<u64>) -> Tensor {
+        let Some((&classes, shape)) = weights.shape().split_last() else { panic!() };
+        assert!(classes > 0);
+        Tensor::new_with_dtype(
+            shape.to_vec(),
+            vec![weights],
+            Box::new(Categorical { seed }),
+            DType::I64,
+        )
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+}
+
+impl TensorOperator for Categorical {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, _context: &mut ForwardGrad) -> Tensor {
+        Tensor::zero(tensor.shape())
+    }
+
+    fn backward_grad(&self, _tensor: &Tensor, _grad: &Tensor, _context: &mut BackwardGrad) {}
+}
+
+#[test]
+fn test() {
+    use crate::cpu::CpuError;
+    use std::sync::Arc;
+
+    let weights = Tensor::constant([2, 3], Arc::new(vec![0.0, 1.0, 0.0, 1.0, 0.0, 3.0]));
+    let x = weights.categorical(None);
+    assert_eq!(x.shape(), [2]);
+    assert_eq!(x.dtype(), DType::I64);
+
+    let mut counts = [0; 3];
+    let mut context = crate::cpu::CpuContext::new();
+    context.set_seed(5);
+    for _ in 0..400 {
+        let x = weights.categorical(None);
+        let value = context.compute(&x).unwrap();
+        assert_eq!(value[0], 1.0);
+        counts[value[1] as usize] += 1;
+    }
+    assert_eq!(counts[1], 0);
+    assert!((250..350).contains(&counts[2]));
+
+    let check = |data: Vec<f32>| {
+        let weights = Tensor::constant([2, 2], Arc::new(data));
+        weights.categorical(Some(0)).compute().unwrap_err()
+    };
+    assert_eq!(
+        check(vec![1.0, 1.0, 0.0, 0.0]),
+        CpuError::InvalidWeights { row: 1 }
+    );
+    assert_eq!(
+        check(vec![2.0, -1.0, 1.0, 0.0]),
+        CpuError::InvalidWeights { row: 0 }
+    );
+    assert_eq!(
+        check(vec![1.0, f32::NAN, 1.0, 0.0]),
+        CpuError::InvalidWeights { row: 0 }
+    );
+}
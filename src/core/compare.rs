@@ -0,0 +1,83 @@
+use std::fmt::{Display, Formatter};
+
+use crate::core::TensorOperator;
+use crate::dtype::DType;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 逐元素比较, 结果为 `DType::Bool`, 成立为 1, 否则为 0
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Compare {
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    Eq,
+}
+
+impl Compare {
+    pub fn apply(self, a: Tensor, b: Tensor) -> Tensor {
+        assert_eq!(a.shape(), b.shape());
+        Tensor::new_with_dtype(a.shape().to_vec(), vec![a, b], Box::new(self), DType::Bool)
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Compare::Gt => " > ",
+            Compare::Lt => " < ",
+            Compare::Ge => " >= ",
+            Compare::Le => " <= ",
+            Compare::Eq => " == ",
+        }
+    }
+}
+
+impl TensorOperator for Compare {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, _context: &mut ForwardGrad) -> Tensor {
+        Tensor::zero(tensor.shape())
+    }
+
+    fn backward_grad(&self, _tensor: &Tensor, _grad: &Tensor, _context: &mut BackwardGrad) {}
+
+    fn display(&self, tensor: &Tensor, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let [a, b] = tensor.arguments() else { panic!() };
+        f.write_str("(")?;
+        Display::fmt(a, f)?;
+        f.write_str(self.symbol())?;
+        Display::fmt(b, f)?;
+        f.write_str(")")?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let a = Tensor::constant([3], Arc::new(vec![1.0, 2.0, 3.0]));
+    let b = Tensor::constant([3], Arc::new(vec![3.0, 2.0, 1.0]));
+    for (c, expect) in [
+        (a.gt(&b), [0.0, 0.0, 1.0]),
+        (a.lt(&b), [1.0, 0.0, 0.0]),
+        (a.ge(&b), [0.0, 1.0, 1.0]),
+        (a.le(&b), [1.0, 1.0, 0.0]),
+        (a.eq(&b), [0.0, 1.0, 0.0]),
+        (a.gt(Tensor::scale(1.5)), [0.0, 1.0, 1.0]),
+    ] {
+        assert_eq!(c.dtype(), DType::Bool);
+        assert_eq!(c.compute().unwrap().as_slice(), expect);
+    }
+
+    // 只有一个元素的非标量与标量
+    let one = Tensor::constant([1], Arc::new(vec![1.0]));
+    let c = one.lt(Tensor::scale(2.0));
+    assert_eq!(c.shape(), [1]);
+    assert_eq!(c.compute().unwrap().as_slice(), [1.0]);
+    let c = Tensor::scale(2.0).le(&one);
+    assert_eq!(c.shape(), [1]);
+    assert_eq!(c.compute().unwrap().as_slice(), [0.0]);
+}
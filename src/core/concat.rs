@@ -0,0 +1,119 @@
+use std::fmt::{Display, Formatter};
+
+use crate::core::TensorOperator;
+use crate::dtype::DType;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 沿第 `axis` 维拼接, 其余各维长度须相同
+#[derive(Debug, Copy, Clone)]
+pub struct Concat {
+    axis: usize,
+}
+
+impl Concat {
+    pub fn concat(tensors: Vec<Tensor>, axis: usize) -> Tensor {
+        assert!(!tensors.is_empty());
+        let mut shape = tensors[0].shape().to_vec();
+        assert!(axis < shape.len());
+        shape[axis] = 0;
+        for t in &tensors {
+            assert_eq!(t.shape().len(), shape.len());
+            for (i, (&a, &b)) in t.shape().iter().zip(&shape).enumerate() {
+                assert!(i == axis || a == b, "concat {:?} to {:?}", t.shape(), shape);
+            }
+            shape[axis] += t.shape()[axis];
+        }
+        let dtype = DType::promote(tensors.iter().map(|x| x.dtype())).unwrap();
+        Tensor::new_with_dtype(shape, tensors, Box::new(Concat { axis }), dtype)
+    }
+
+    pub fn axis(&self) -> usize {
+        self.axis
+    }
+}
+
+impl TensorOperator for Concat {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let grads = tensor
+            .arguments()
+            .iter()
+            .map(|x| context.compute(x))
+            .collect();
+        Concat::concat(grads, self.axis)
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let sizes = tensor
+            .arguments()
+            .iter()
+            .map(|x| x.shape()[self.axis])
+            .collect::<Vec<_>>();
+        for (arg, g) in tensor.arguments().iter().zip(grad.split(&sizes, self.axis)) {
+            context.append(arg, g);
+        }
+    }
+
+    fn display(&self, tensor: &Tensor, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "concat{}[", self.axis)?;
+        for (i, arg) in tensor.arguments().iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            Display::fmt(arg, f)?;
+        }
+        f.write_str("]")
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let a = Tensor::constant([2, 2], Arc::new(vec![1.0, 2.0, 3.0, 4.0]));
+    let b = Tensor::constant([2, 1], Arc::new(vec![5.0, 6.0]));
+    let c = Tensor::concat([&a, &b], 1);
+    assert_eq!(c.shape(), [2, 3]);
+    assert_eq!(
+        c.compute().unwrap().as_slice(),
+        [1.0, 2.0, 5.0, 3.0, 4.0, 6.0]
+    );
+
+    let s = Tensor::stack([&b, &b], 2);
+    assert_eq!(s.shape(), [2, 1, 2]);
+    assert_eq!(s.compute().unwrap().as_slice(), [5.0, 5.0, 6.0, 6.0]);
+
+    let [x, y] = &c.split(&[1, 2], 1)[..] else { panic!() };
+    assert_eq!(x.compute().unwrap().as_slice(), [1.0, 3.0]);
+    assert_eq!(y.compute().unwrap().as_slice(), [2.0, 5.0, 4.0, 6.0]);
+
+    let chunks = c.chunk(2, 1);
+    assert_eq!(
+        chunks
+            .iter()
+            .map(|x| x.shape().to_vec())
+            .collect::<Vec<_>>(),
+        [[2, 2], [2, 1]]
+    );
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([2, 2], Arc::new(vec![1.0, -2.0, 0.5, 1.5]));
+    let b = Tensor::constant([2, 3], Arc::new(vec![0.3, 1.5, -1.0, 2.0, 0.1, -0.7]));
+    let check = gradcheck(
+        |x| {
+            let c = Tensor::concat(x, 1);
+            &c * &c
+        },
+        &[a, b],
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
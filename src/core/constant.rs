@@ -2,26 +2,77 @@ use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 
 use crate::core::TensorOperator;
+use crate::dtype::DType;
+use crate::element::Element;
 use crate::grad::{BackwardGrad, ForwardGrad};
 use crate::tensor::Tensor;
 
+/// 常量的数据, 计算时转换为上下文的元素类型
+#[derive(Debug, Clone)]
+pub enum ConstantData {
+    F32(Arc<Vec<f32>>),
+    F64(Arc<Vec<f64>>),
+    I64(Arc<Vec<i64>>),
+    Bool(Arc<Vec<bool>>),
+}
+
+impl ConstantData {
+    pub fn len(&self) -> usize {
+        match self {
+            ConstantData::F32(data) => data.len(),
+            ConstantData::F64(data) => data.len(),
+            ConstantData::I64(data) => data.len(),
+            ConstantData::Bool(data) => data.len(),
+        }
+    }
+
+    pub fn dtype(&self) -> DType {
+        match self {
+            ConstantData::F32(_) => DType::F32,
+            ConstantData::F64(_) => DType::F64,
+            ConstantData::I64(_) => DType::I64,
+            ConstantData::Bool(_) => DType::Bool,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Constant {
-    data: Arc<Vec<f32>>,
+    data: ConstantData,
 }
 
 impl Constant {
     pub fn constant(shape: Vec<usize>, data: Arc<Vec<f32>>) -> Tensor {
-        Tensor::new(shape, vec![], Box::new(Self { data }))
+        Self::from_data(shape, ConstantData::F32(data))
+    }
+
+    pub fn constant_f64(shape: Vec<usize>, data: Arc<Vec<f64>>) -> Tensor {
+        Self::from_data(shape, ConstantData::F64(data))
+    }
+
+    pub fn from_data(shape: Vec<usize>, data: ConstantData) -> Tensor {
+        let dtype = data.dtype();
+        Tensor::new_with_dtype(shape, vec![], Box::new(Self { data }), dtype)
     }
 
     pub fn scale(value: f32) -> Tensor {
-        let data = Arc::new(vec![value]);
-        Tensor::new(vec![], vec![], Box::new(Self { data }))
+        Self::constant(vec![], Arc::new(vec![value]))
+    }
+
+    pub fn scale_f64(value: f64) -> Tensor {
+        Self::constant_f64(vec![], Arc::new(vec![value]))
     }
 
     pub fn data(&self) -> Arc<Vec<f32>> {
-        self.data.clone()
+        self.data_as()
+    }
+
+    pub fn data_as<T: Element>(&self) -> Arc<Vec<T>> {
+        T::from_data(&self.data)
     }
 }
 
@@ -38,8 +89,13 @@ impl TensorOperator for Constant {
         f.write_str("Constant")?;
         tensor.shape().fmt(f)?;
         f.write_str("{")?;
-        if self.data.len() > 0 {
-            Debug::fmt(&self.data[0], f)?;
+        if !self.data.is_empty() {
+            match &self.data {
+                ConstantData::F32(data) => Debug::fmt(&data[0], f)?,
+                ConstantData::F64(data) => Debug::fmt(&data[0], f)?,
+                ConstantData::I64(data) => Debug::fmt(&data[0], f)?,
+                ConstantData::Bool(data) => Debug::fmt(&data[0], f)?,
+            }
             if self.data.len() > 1 {
                 f.write_str(", ... ")?;
             }
@@ -48,3 +104,33 @@ impl TensorOperator for Constant {
         Ok(())
     }
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+
+    let a = Tensor::constant([2], Arc::new(vec![1.0, -2.0]));
+    let c = Tensor::constant([2], Arc::new(vec![3.0, 4.0]));
+    let check = gradcheck(|x| &x[0] * &c, &[a]);
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
+
+#[test]
+fn test() {
+    use crate::cpu::CpuContext;
+
+    let a = Tensor::constant_f64([2], Arc::new(vec![0.1, 1e-12]));
+    let mut context = CpuContext::<f64>::default();
+    assert_eq!(
+        context.compute(&(&a + &a)).unwrap().as_slice(),
+        [0.2, 2e-12]
+    );
+    assert_eq!(a.compute().unwrap().as_slice(), [0.1f32, 1e-12]);
+
+    let i = Tensor::indices([3], Arc::new(vec![0, 7, -2]));
+    assert_eq!(i.dtype(), DType::I64);
+    assert_eq!(i.compute().unwrap().as_slice(), [0.0, 7.0, -2.0]);
+    let m = Tensor::mask([2], Arc::new(vec![true, false]));
+    assert_eq!(m.dtype(), DType::Bool);
+    assert_eq!(m.compute().unwrap().as_slice(), [1.0, 0.0]);
+}
@@ -0,0 +1,199 @@
+use crate::core::linear_scan::LinearScan;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Scan {
+    Sum,
+    Prod,
+}
+
+/// 沿第 `axis` 维的前缀和或前缀积
+///
+/// `exclusive` 时不含当前元素 (第一个为 0 或 1), `reverse` 时从后向前累计.
+#[derive(Debug, Copy, Clone)]
+pub struct Cumulative {
+    pub axis: usize,
+    pub scan: Scan,
+    pub exclusive: bool,
+    pub reverse: bool,
+}
+
+impl Cumulative {
+    pub fn sum(axis: usize) -> Self {
+        Cumulative {
+            axis,
+            scan: Scan::Sum,
+            exclusive: false,
+            reverse: false,
+        }
+    }
+
+    pub fn prod(axis: usize) -> Self {
+        Cumulative {
+            scan: Scan::Prod,
+            ..Cumulative::sum(axis)
+        }
+    }
+
+    /// 不含当前元素
+    pub fn exclusive(self) -> Self {
+        Cumulative {
+            exclusive: true,
+            ..self
+        }
+    }
+
+    /// 从后向前累计
+    pub fn reverse(self) -> Self {
+        Cumulative {
+            reverse: true,
+            ..self
+        }
+    }
+
+    pub fn apply(self, tensor: Tensor) -> Tensor {
+        assert!(self.axis < tensor.shape().len());
+        Tensor::new(tensor.shape().to_vec(), vec![tensor], Box::new(self))
+    }
+
+    /// 方向相反的累计
+    fn flipped(self) -> Self {
+        Cumulative {
+            reverse: !self.reverse,
+            ..self
+        }
+    }
+
+    /// 与自身累计范围相同的前缀和
+    fn linear(self) -> Self {
+        Cumulative {
+            scan: Scan::Sum,
+            ..self
+        }
+    }
+
+    /// 方向相同的线性递推, 前缀积 `y` 满足 `y[i] = x[i] * y[i - 1]`
+    fn recurrence(self) -> LinearScan {
+        LinearScan {
+            axis: self.axis,
+            reverse: self.reverse,
+            shifted: false,
+        }
+    }
+}
+
+impl TensorOperator for Cumulative {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [arg] = tensor.arguments() else { panic!() };
+        let grad = context.compute(arg);
+        // 前缀积的导数也满足线性递推, 不用除以输入, 输入为零时也成立
+        let scan = self.recurrence();
+        match (self.scan, self.exclusive) {
+            (Scan::Sum, _) => self.apply(grad),
+            (Scan::Prod, false) => {
+                let exclusive = self.exclusive().apply(arg.clone());
+                scan.apply(arg.clone(), grad * exclusive)
+            }
+            (Scan::Prod, true) => {
+                let grad = scan.shift(grad * tensor);
+                scan.shifted().apply(arg.clone(), grad)
+            }
+        }
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [arg] = tensor.arguments() else { panic!() };
+        // 输出的第 i 个元素包含输入的第 j 个, 当且仅当反向累计时第 j 个包含第 i 个
+        let back = self.linear().flipped();
+        let scan = self.recurrence();
+        let grad = match (self.scan, self.exclusive) {
+            (Scan::Sum, _) => back.apply(grad.clone()),
+            (Scan::Prod, false) => {
+                let grad = scan.transpose().apply(arg.clone(), grad.clone());
+                grad * self.exclusive().apply(arg.clone())
+            }
+            (Scan::Prod, true) => {
+                let back = self.flipped().recurrence();
+                let grad = back.apply(arg.clone(), grad.clone());
+                back.shift(grad * scan.shift(tensor.clone()))
+            }
+        };
+        context.append(arg, grad);
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 3], Arc::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    let check = |y: Tensor, expect: [f32; 6]| {
+        assert_eq!(y.compute().unwrap().as_slice(), expect);
+    };
+    check(x.cumsum(1), [1.0, 3.0, 6.0, 4.0, 9.0, 15.0]);
+    check(x.cumsum(0), [1.0, 2.0, 3.0, 5.0, 7.0, 9.0]);
+    check(x.cumprod(1), [1.0, 2.0, 6.0, 4.0, 20.0, 120.0]);
+    let sum = Cumulative::sum(1);
+    check(
+        sum.exclusive().apply(x.clone()),
+        [0.0, 1.0, 3.0, 0.0, 4.0, 9.0],
+    );
+    check(
+        sum.reverse().apply(x.clone()),
+        [6.0, 5.0, 3.0, 15.0, 11.0, 6.0],
+    );
+    check(
+        sum.exclusive().reverse().apply(x.clone()),
+        [5.0, 3.0, 0.0, 11.0, 6.0, 0.0],
+    );
+    check(
+        Cumulative::prod(1).exclusive().apply(x.clone()),
+        [1.0, 1.0, 2.0, 1.0, 4.0, 20.0],
+    );
+    check(
+        sum.reverse().reverse().apply(x.clone()),
+        [6.0, 5.0, 3.0, 15.0, 11.0, 6.0],
+    );
+}
+
+#[test]
+fn grad_check() {
+    use crate::core::sum_scale::SumScale;
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 3], Arc::new(vec![1.0, -2.0, 3.0, 0.5, 1.5, -1.0]));
+    let zeros = Tensor::constant([2, 3], Arc::new(vec![0.0, 2.0, 3.0, 1.5, 0.0, 0.0]));
+    for scan in [Cumulative::sum(1), Cumulative::prod(1), Cumulative::prod(0)] {
+        for scan in [
+            scan,
+            scan.exclusive(),
+            scan.reverse(),
+            scan.exclusive().reverse(),
+        ] {
+            for x in [&x, &zeros] {
+                let check = gradcheck(|x| scan.apply(x[0].clone()), std::slice::from_ref(x));
+                assert!(check.max_error() < 1e-2, "{:?} {:?}", scan, check);
+            }
+        }
+    }
+
+    // 二阶导数同样不除以输入
+    for scan in [
+        Cumulative::prod(1),
+        Cumulative::prod(1).exclusive().reverse(),
+    ] {
+        let grad = |x: &[Tensor]| {
+            let y = scan.apply(x[0].clone());
+            SumScale::sum(&y * &y).grads(x).remove(0)
+        };
+        let check = gradcheck(grad, std::slice::from_ref(&zeros));
+        assert!(check.max_error() < 1e-2, "{:?} {:?}", scan, check);
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::{data_size, Tensor};
+
+/// 由输入数据计算输出数据
+pub type CustomForward = dyn Fn(&[&[f32]]) -> Vec<f32> + Send + Sync;
+/// 由 (输入, 输出, 输出的梯度) 构造各输入的梯度
+pub type CustomBackward = dyn Fn(&[Tensor], &Tensor, &Tensor) -> Vec<Tensor> + Send + Sync;
+/// 由 (输入, 输出, 各输入的切向量) 构造输出的切向量
+pub type CustomForwardGrad = dyn Fn(&[Tensor], &Tensor, &[Tensor]) -> Tensor + Send + Sync;
+
+/// 用户以闭包定义的算子
+///
+/// ```ignore
+/// let square = CustomOp::new(
+///     "square",
+///     |x| x[0].iter().map(|v| v * v).collect(),
+///     |x, _, grad| vec![grad * &x[0] * 2.0],
+/// );
+/// let y = square.apply(x.shape(), vec![x]);
+/// ```
+#[derive(Clone)]
+pub struct CustomOp {
+    name: Arc<str>,
+    forward: Arc<CustomForward>,
+    backward: Arc<CustomBackward>,
+    forward_grad: Option<Arc<CustomForwardGrad>>,
+}
+
+impl CustomOp {
+    pub fn new<F, B>(name: &str, forward: F, backward: B) -> Self
+    where
+        F: Fn(&[&[f32]]) -> Vec<f32> + Send + Sync + 'static,
+        B: Fn(&[Tensor], &Tensor, &Tensor) -> Vec<Tensor> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            forward: Arc::new(forward),
+            backward: Arc::new(backward),
+            forward_grad: None,
+        }
+    }
+
+    /// 提供前向模式的求导规则, 否则不支持 `ForwardGrad`
+    pub fn with_forward_grad<J>(mut self, forward_grad: J) -> Self
+    where
+        J: Fn(&[Tensor], &Tensor, &[Tensor]) -> Tensor + Send + Sync + 'static,
+    {
+        self.forward_grad = Some(Arc::new(forward_grad));
+        self
+    }
+
+    pub fn apply<S: AsRef<[usize]>>(&self, shape: S, arguments: Vec<Tensor>) -> Tensor {
+        Tensor::new(shape.as_ref().to_vec(), arguments, Box::new(self.clone()))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn forward(&self, input: &[&[f32]], len: usize) -> Vec<f32> {
+        let output = (self.forward)(input);
+        assert_eq!(
+            output.len(),
+            len,
+            "the custom operator {} output",
+            self.name
+        );
+        output
+    }
+}
+
+impl Debug for CustomOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CustomOp<{}>", self.name)
+    }
+}
+
+impl TensorOperator for CustomOp {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(self.clone())
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let Some(forward_grad) = &self.forward_grad else {
+            panic!("the custom operator {} has no forward grad", self.name)
+        };
+        let grads = tensor
+            .arguments()
+            .iter()
+            .map(|x| context.compute(x))
+            .collect::<Vec<_>>();
+        let r = forward_grad(tensor.arguments(), tensor, &grads);
+        assert_eq!(r.shape(), tensor.shape());
+        r
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let grads = (self.backward)(tensor.arguments(), tensor, grad);
+        assert_eq!(grads.len(), tensor.arguments().len());
+        for (arg, g) in tensor.arguments().iter().zip(grads) {
+            assert_eq!(data_size(arg.shape()), data_size(g.shape()));
+            context.append(arg, g.reshape(arg.shape()));
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use crate::tools::grad_check::gradcheck;
+
+    let mul = CustomOp::new(
+        "mul",
+        |x| x[0].iter().zip(x[1]).map(|(a, b)| a * b).collect(),
+        |x, _, grad| vec![grad * &x[1], grad * &x[0]],
+    )
+    .with_forward_grad(|x, _, g| &g[0] * &x[1] + &x[0] * &g[1]);
+
+    let a = Tensor::constant([2], Arc::new(vec![1.0, -2.0]));
+    let b = Tensor::constant([2], Arc::new(vec![3.0, 0.5]));
+    let y = mul.apply([2], vec![a.clone(), b.clone()]);
+    assert_eq!(y.compute().unwrap().as_slice(), [3.0, -1.0]);
+    assert_eq!(y.back(&a).compute().unwrap().as_slice(), [3.0, 0.5]);
+
+    let check = gradcheck(|x| mul.apply([2], x.to_vec()).powf(2.0), &[a, b]);
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
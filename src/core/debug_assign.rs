@@ -43,3 +43,16 @@ impl TensorOperator for DebugAssign {
         Display::fmt(warp, f)
     }
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([2], Arc::new(vec![1.0, -2.0]));
+    let check = gradcheck(
+        |x| DebugAssign::debug("grad_check".to_string(), x[0].clone()).powf(2.0),
+        &[a],
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
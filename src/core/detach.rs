@@ -0,0 +1,33 @@
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 值与输入相同, 但不传递梯度
+#[derive(Debug, Copy, Clone)]
+pub struct Detach;
+
+impl Detach {
+    pub fn detach(tensor: Tensor) -> Tensor {
+        Tensor::new(tensor.shape().to_vec(), vec![tensor], Box::new(Detach))
+    }
+}
+
+impl TensorOperator for Detach {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, _context: &mut ForwardGrad) -> Tensor {
+        Tensor::zero(tensor.shape())
+    }
+
+    fn backward_grad(&self, _tensor: &Tensor, _grad: &Tensor, _context: &mut BackwardGrad) {}
+}
+
+#[test]
+fn test() {
+    let a = Tensor::scale(3.0);
+    let y = &a * a.detach();
+    assert_eq!(y.compute().unwrap().as_slice(), [9.0]);
+    assert_eq!(y.back(&a).compute().unwrap().as_slice(), [3.0]);
+}
@@ -49,3 +49,14 @@ fn test() {
     assert_eq!(y.back(a).compute().unwrap().as_slice(), [0.5]);
     assert_eq!(y.back(b).compute().unwrap().as_slice(), [-0.25]);
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([3], Arc::new(vec![1.0, -2.0, 0.5]));
+    let b = Tensor::constant([3], Arc::new(vec![2.0, 1.5, -3.0]));
+    let check = gradcheck(|x| &x[0] / &x[1], &[a, b]);
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
@@ -0,0 +1,35 @@
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 随机的 dropout 掩码, 以概率 `p` 为 0, 否则为 `1 / (1 - p)`
+///
+/// 没有参数, 每次由后端计算时重新抽取; 同一次计算中前向与反向共用同一个结果.
+/// 后端不在训练模式时全为 1.
+#[derive(Debug, Copy, Clone)]
+pub struct DropoutMask {
+    p: f32,
+}
+
+impl DropoutMask {
+    pub fn tensor<S: AsRef<[usize]>>(shape: S, p: f32) -> Tensor {
+        assert!((0.0..1.0).contains(&p));
+        Tensor::new(shape.as_ref().to_vec(), vec![], Box::new(DropoutMask { p }))
+    }
+
+    pub fn p(&self) -> f32 {
+        self.p
+    }
+}
+
+impl TensorOperator for DropoutMask {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, _context: &mut ForwardGrad) -> Tensor {
+        Tensor::zero(tensor.shape())
+    }
+
+    fn backward_grad(&self, _tensor: &Tensor, _grad: &Tensor, _context: &mut BackwardGrad) {}
+}
@@ -0,0 +1,82 @@
+use crate::core::index_add::IndexAdd;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::{data_size, Tensor};
+
+/// 以任意形状的 `indices` 查找 `[vocab, dim]` 的表, 输出形状为 `[.., dim]`
+///
+/// 关于表的梯度是 `IndexAdd`, `ModelContext::optimization` 据此只更新用到的行.
+#[derive(Debug, Copy, Clone)]
+pub struct EmbeddingLookup;
+
+impl EmbeddingLookup {
+    pub fn tensor(table: Tensor, indices: Tensor) -> Tensor {
+        let &[_, dim] = table.shape() else { panic!("the table must be [vocab, dim]") };
+        assert!(indices.dtype().is_integer(), "the indices must be integers");
+        let mut shape = indices.shape().to_vec();
+        shape.push(dim);
+        let dtype = table.dtype();
+        Tensor::new_with_dtype(
+            shape,
+            vec![table, indices],
+            Box::new(EmbeddingLookup),
+            dtype,
+        )
+    }
+}
+
+impl TensorOperator for EmbeddingLookup {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [table, indices] = tensor.arguments() else { panic!() };
+        EmbeddingLookup::tensor(context.compute(table), indices.clone())
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [table, indices] = tensor.arguments() else { panic!() };
+        let &[vocab, dim] = table.shape() else { panic!() };
+        let n = data_size(indices.shape());
+        context.append(
+            table,
+            IndexAdd::tensor(0, indices.reshape([n]), grad.reshape([n, dim]), vocab),
+        );
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let table = Tensor::constant([3, 2], Arc::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    let indices = Tensor::indices([2, 2], Arc::new(vec![2, 0, 2, 1]));
+    let y = table.embedding(&indices);
+    assert_eq!(y.shape(), [2, 2, 2]);
+    assert_eq!(
+        y.compute().unwrap().as_slice(),
+        [5.0, 6.0, 1.0, 2.0, 5.0, 6.0, 3.0, 4.0]
+    );
+    assert_eq!(
+        y.back(&table).compute().unwrap().as_slice(),
+        [1.0, 1.0, 1.0, 1.0, 2.0, 2.0]
+    );
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let table = Tensor::constant([3, 2], Arc::new(vec![1.0, -2.0, 3.0, 0.5, 1.5, -1.0]));
+    let indices = Tensor::indices([4], Arc::new(vec![2, 0, 2, 1]));
+    let check = gradcheck(
+        |x| {
+            let y = x[0].embedding(&indices);
+            &y * &y
+        },
+        std::slice::from_ref(&table),
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
@@ -38,6 +38,19 @@ impl TensorOperator for ExtendScale {
 
     fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
         let [arg] = tensor.arguments() else { panic!() };
-        context.append(arg, SumScale::sum(grad.clone()));
+        context.append(
+            arg,
+            SumScale::sum_to_shape(grad.clone(), arg.shape().to_vec()),
+        );
     }
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([1], Arc::new(vec![1.5]));
+    let check = gradcheck(|x| ExtendScale::extend(x[0].clone(), vec![2, 3]), &[a]);
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
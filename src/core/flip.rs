@@ -0,0 +1,72 @@
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::{map_offsets, Tensor};
+
+/// 把 `axes` 中的各维反转
+#[derive(Debug, Clone)]
+pub struct Flip {
+    axes: Vec<usize>,
+}
+
+impl Flip {
+    pub fn tensor(input: Tensor, axes: Vec<usize>) -> Tensor {
+        assert!(axes.iter().all(|&x| x < input.shape().len()));
+        Tensor::new(input.shape().to_vec(), vec![input], Box::new(Flip { axes }))
+    }
+
+    /// 输出的每个元素对应的输入位置
+    pub fn offsets(&self, shape: &[usize]) -> Vec<usize> {
+        map_offsets(shape, shape, |axis, i| {
+            Some(if self.axes.contains(&axis) {
+                shape[axis] - 1 - i
+            } else {
+                i
+            })
+        })
+        .into_iter()
+        .map(Option::unwrap)
+        .collect()
+    }
+}
+
+impl TensorOperator for Flip {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(self.clone())
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [arg] = tensor.arguments() else { panic!() };
+        Flip::tensor(context.compute(arg), self.axes.clone())
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [arg] = tensor.arguments() else { panic!() };
+        context.append(arg, Flip::tensor(grad.clone(), self.axes.clone()));
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 3], Arc::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    assert_eq!(
+        x.flip(&[1]).compute().unwrap().as_slice(),
+        [3.0, 2.0, 1.0, 6.0, 5.0, 4.0]
+    );
+    assert_eq!(
+        x.flip(&[0, 1]).compute().unwrap().as_slice(),
+        [6.0, 5.0, 4.0, 3.0, 2.0, 1.0]
+    );
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 3], Arc::new(vec![1.0, -2.0, 3.0, 0.5, 1.5, -1.0]));
+    let w = Tensor::constant([2, 3], Arc::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    let check = gradcheck(|x| x[0].flip(&[0]) * &w * &x[0], &[x]);
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
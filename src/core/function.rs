@@ -1,7 +1,11 @@
+use crate::core::extend_scale::ExtendScale;
 use crate::core::TensorOperator;
 use crate::grad::{BackwardGrad, ForwardGrad};
 use crate::tensor::Tensor;
 
+/// 逐元素的一元函数
+///
+/// 标量参数以 f64 保存, 以 f32 计算时才舍入.
 #[derive(Debug, Copy, Clone)]
 pub enum Function {
     Sin,
@@ -17,20 +21,35 @@ pub enum Function {
     /// 取负函数
     Neg,
     /// 乘以标量
-    Mul(f32),
+    Mul(f64),
     /// 加上标量
-    Add(f32),
+    Add(f64),
     /// 幂函数
-    Pow(f32),
+    Pow(f64),
     Sigmoid,
+    /// 指数函数
+    Exp,
+    /// 自然对数
+    Ln,
+    /// 限制在 [min, max] 中, 只在区间内 (含端点) 传递梯度
+    Clamp(f64, f64),
 }
 
 impl Function {
     pub fn apply(self, tensor: Tensor) -> Tensor {
+        if let Function::Clamp(min, max) = self {
+            assert!(min <= max);
+        }
         Tensor::new(tensor.shape().to_vec(), vec![tensor], Box::new(self))
     }
 }
 
+/// `Clamp` 不截断的位置为 1
+fn clamp_mask(arg: &Tensor, min: f64, max: f64) -> Tensor {
+    let bound = |x: f64| ExtendScale::extend(Tensor::scale_f64(x), arg.shape().to_vec());
+    arg.ge(bound(min)) * arg.le(bound(max))
+}
+
 impl TensorOperator for Function {
     fn clone_box(&self) -> Box<dyn TensorOperator> {
         Box::new(self.clone())
@@ -47,7 +66,7 @@ impl TensorOperator for Function {
             Function::Abs => grad * arg.apply(Function::Sig),
             Function::Sig => Tensor::zero(tensor.shape()),
             Function::Neg => -grad,
-            Function::Mul(x) => grad * x,
+            Function::Mul(x) => grad.apply(Function::Mul(x)),
             Function::Add(_) => grad.clone(),
             Function::Pow(x) => {
                 if x == 0.0 {
@@ -57,10 +76,13 @@ impl TensorOperator for Function {
                 } else if x == 2.0 {
                     grad * arg * 2.0
                 } else {
-                    grad * arg.powf(x - 1.0) * x
+                    (grad * arg.powf(x - 1.0)).apply(Function::Mul(x))
                 }
             }
             Function::Sigmoid => grad * (tensor * (-tensor + 1.0)),
+            Function::Exp => grad * tensor,
+            Function::Ln => grad * arg.powf(-1.0),
+            Function::Clamp(min, max) => grad * clamp_mask(arg, min, max),
         }
     }
 
@@ -74,7 +96,7 @@ impl TensorOperator for Function {
             Function::Abs => grad * arg.apply(Function::Sig),
             Function::Sig => Tensor::zero(tensor.shape()),
             Function::Neg => -grad,
-            Function::Mul(x) => grad * x,
+            Function::Mul(x) => grad.apply(Function::Mul(x)),
             Function::Add(_) => grad.clone(),
             Function::Pow(x) => {
                 if x == 0.0 {
@@ -84,11 +106,79 @@ impl TensorOperator for Function {
                 } else if x == 2.0 {
                     grad * arg * 2.0
                 } else {
-                    grad * arg.powf(x - 1.0) * x
+                    (grad * arg.powf(x - 1.0)).apply(Function::Mul(x))
                 }
             }
             Function::Sigmoid => grad * (tensor * (-tensor + 1.0)),
+            Function::Exp => grad * tensor,
+            Function::Ln => grad * arg.powf(-1.0),
+            Function::Clamp(min, max) => grad * clamp_mask(arg, min, max),
         };
         context.append(arg, back);
     }
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    // 避开不可导点
+    let a = Tensor::constant([4], Arc::new(vec![0.7, -1.3, 2.1, -0.4]));
+    let positive = Tensor::constant([3], Arc::new(vec![0.7, 1.3, 2.1]));
+    for fun in [
+        Function::Sin,
+        Function::Cos,
+        Function::ReLU,
+        Function::Step,
+        Function::Abs,
+        Function::Sig,
+        Function::Neg,
+        Function::Mul(3.0),
+        Function::Add(2.0),
+        Function::Pow(0.0),
+        Function::Pow(1.0),
+        Function::Pow(2.0),
+        Function::Pow(3.0),
+        Function::Sigmoid,
+        Function::Exp,
+        Function::Clamp(-1.0, 1.0),
+    ] {
+        let check = gradcheck(|x| x[0].apply(fun), std::slice::from_ref(&a));
+        assert!(check.max_error() < 1e-2, "{:?} {:?}", fun, check);
+    }
+    for fun in [Function::Pow(0.5), Function::Pow(-1.0), Function::Ln] {
+        let check = gradcheck(|x| x[0].apply(fun), std::slice::from_ref(&positive));
+        assert!(check.max_error() < 1e-2, "{:?} {:?}", fun, check);
+    }
+}
+
+#[test]
+fn clamp() {
+    use std::sync::Arc;
+
+    let x = Tensor::constant([5], Arc::new(vec![-2.0, -1.0, 0.5, 1.0, 3.0]));
+    let y = x.clamp(-1.0, 1.0);
+    assert_eq!(y.compute().unwrap().as_slice(), [-1.0, -1.0, 0.5, 1.0, 1.0]);
+    assert_eq!(
+        y.back(&x).compute().unwrap().as_slice(),
+        [0.0, 1.0, 1.0, 1.0, 0.0]
+    );
+
+    // 只有一个元素的非标量
+    for shape in [vec![1], vec![1, 1]] {
+        let x = Tensor::constant(&shape, Arc::new(vec![2.0]));
+        let y = x.clamp(-1.0, 1.0);
+        assert_eq!(y.compute().unwrap().as_slice(), [1.0]);
+        assert_eq!(y.back(&x).compute().unwrap().as_slice(), [0.0]);
+        let d = Tensor::constant(&shape, Arc::new(vec![1.0]));
+        let jvp = ForwardGrad::new(&[(x, d)]).compute(&y);
+        assert_eq!(jvp.compute().unwrap().as_slice(), [0.0]);
+    }
+
+    // f64 下按 f64 的端点判断是否截断
+    let x = Tensor::constant_f64([1], Arc::new(vec![0.1]));
+    let y = x.clamp(0.1, 1.0);
+    let mut context = crate::cpu::CpuContext::<f64>::default();
+    assert_eq!(context.compute(&y.back(&x)).unwrap().as_slice(), [1.0]);
+}
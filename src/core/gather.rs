@@ -0,0 +1,106 @@
+use crate::core::scatter_add::ScatterAdd;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// `output[.., i, ..] = input[.., indices[.., i, ..], ..]`, 下标位于第 `axis` 维
+///
+/// `indices` 与 `input` 维数相同, 除 `axis` 外各维长度相等, 输出形状与 `indices` 相同.
+#[derive(Debug, Copy, Clone)]
+pub struct Gather {
+    axis: usize,
+}
+
+impl Gather {
+    pub fn tensor(axis: usize, input: Tensor, indices: Tensor) -> Tensor {
+        check_shape(axis, input.shape(), indices.shape());
+        assert!(indices.dtype().is_integer(), "the indices must be integers");
+        Tensor::new_with_dtype(
+            indices.shape().to_vec(),
+            vec![input.clone(), indices],
+            Box::new(Gather { axis }),
+            input.dtype(),
+        )
+    }
+
+    pub fn axis(&self) -> usize {
+        self.axis
+    }
+}
+
+/// 除 `axis` 外各维长度相等
+pub(crate) fn check_shape(axis: usize, a: &[usize], b: &[usize]) {
+    assert_eq!(a.len(), b.len());
+    assert!(axis < a.len());
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        assert!(
+            i == axis || x == y,
+            "shape {:?} and {:?} at axis {}",
+            a,
+            b,
+            i
+        );
+    }
+}
+
+impl TensorOperator for Gather {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [input, indices] = tensor.arguments() else { panic!() };
+        Gather::tensor(self.axis, context.compute(input), indices.clone())
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [input, indices] = tensor.arguments() else { panic!() };
+        context.append(
+            input,
+            ScatterAdd::tensor(
+                self.axis,
+                indices.clone(),
+                grad.clone(),
+                input.shape().to_vec(),
+            ),
+        );
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 3], Arc::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    let i = Tensor::indices([2, 2], Arc::new(vec![2, 0, 1, 1]));
+    assert_eq!(
+        x.gather(1, &i).compute().unwrap().as_slice(),
+        [3.0, 1.0, 5.0, 5.0]
+    );
+    let i = Tensor::indices([1, 3], Arc::new(vec![1, 0, 1]));
+    assert_eq!(
+        x.gather(0, &i).compute().unwrap().as_slice(),
+        [4.0, 2.0, 6.0]
+    );
+
+    let i = Tensor::indices([2, 1], Arc::new(vec![0, 3]));
+    assert_eq!(
+        x.gather(1, &i).compute(),
+        Err(crate::cpu::CpuError::IndexOutOfBounds {
+            position: 1,
+            index: 3,
+            size: 3
+        })
+    );
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 3], Arc::new(vec![1.0, -2.0, 3.0, 0.5, 1.5, -1.0]));
+    let i = Tensor::indices([2, 4], Arc::new(vec![2, 0, 2, 1, 1, 1, 0, 2]));
+    let check = gradcheck(|x| x[0].gather(1, &i) * x[0].gather(1, &i), &[x]);
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
@@ -0,0 +1,88 @@
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::{map_offsets, Tensor};
+
+/// 把每个元素替换为它所在组的平均值, 只有 `axes` 中各维下标不同的元素属于同一组
+///
+/// 输出形状与输入相同. 这是对称的线性变换, 梯度就是它本身.
+#[derive(Debug, Clone)]
+pub struct GroupMean {
+    axes: Vec<usize>,
+}
+
+impl GroupMean {
+    pub fn tensor(input: Tensor, axes: Vec<usize>) -> Tensor {
+        assert!(axes.iter().all(|&x| x < input.shape().len()));
+        Tensor::new(
+            input.shape().to_vec(),
+            vec![input],
+            Box::new(GroupMean { axes }),
+        )
+    }
+
+    pub fn axes(&self) -> &[usize] {
+        &self.axes
+    }
+}
+
+/// 每个元素所在的组, 以及组数和每组的大小
+pub fn groups(shape: &[usize], axes: &[usize]) -> (Vec<usize>, usize, usize) {
+    let reduced = shape
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| if axes.contains(&i) { 1 } else { x })
+        .collect::<Vec<_>>();
+    let group = map_offsets(shape, &reduced, |axis, i| {
+        Some(if axes.contains(&axis) { 0 } else { i })
+    })
+    .into_iter()
+    .map(Option::unwrap)
+    .collect::<Vec<_>>();
+    let count = reduced.iter().product::<usize>();
+    let size = group.len().checked_div(count).unwrap_or(0);
+    (group, count, size)
+}
+
+impl TensorOperator for GroupMean {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(self.clone())
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [arg] = tensor.arguments() else { panic!() };
+        GroupMean::tensor(context.compute(arg), self.axes.clone())
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [arg] = tensor.arguments() else { panic!() };
+        context.append(arg, GroupMean::tensor(grad.clone(), self.axes.clone()));
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 3], Arc::new(vec![1.0, 2.0, 3.0, 5.0, 6.0, 7.0]));
+    assert_eq!(
+        GroupMean::tensor(x.clone(), vec![1])
+            .compute()
+            .unwrap()
+            .as_slice(),
+        [2.0, 2.0, 2.0, 6.0, 6.0, 6.0]
+    );
+    assert_eq!(
+        GroupMean::tensor(x.clone(), vec![0])
+            .compute()
+            .unwrap()
+            .as_slice(),
+        [3.0, 4.0, 5.0, 3.0, 4.0, 5.0]
+    );
+    assert_eq!(
+        GroupMean::tensor(x, vec![0, 1])
+            .compute()
+            .unwrap()
+            .as_slice(),
+        [4.0; 6]
+    );
+}
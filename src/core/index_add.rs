@@ -0,0 +1,86 @@
+use crate::core::index_select::IndexSelect;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// `IndexSelect` 的伴随: 从零开始, 把 `source` 第 `axis` 维的第 i 个切片加到第 `indices[i]` 个上
+#[derive(Debug, Copy, Clone)]
+pub struct IndexAdd {
+    axis: usize,
+}
+
+impl IndexAdd {
+    /// `size` 为输出在第 `axis` 维上的长度
+    pub fn tensor(axis: usize, indices: Tensor, source: Tensor, size: usize) -> Tensor {
+        let &[len] = indices.shape() else { panic!("the indices must be 1-d") };
+        assert!(indices.dtype().is_integer(), "the indices must be integers");
+        assert_eq!(source.shape()[axis], len);
+        let mut shape = source.shape().to_vec();
+        shape[axis] = size;
+        let dtype = source.dtype();
+        Tensor::new_with_dtype(
+            shape,
+            vec![indices, source],
+            Box::new(IndexAdd { axis }),
+            dtype,
+        )
+    }
+
+    pub fn axis(&self) -> usize {
+        self.axis
+    }
+}
+
+impl TensorOperator for IndexAdd {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [indices, source] = tensor.arguments() else { panic!() };
+        IndexAdd::tensor(
+            self.axis,
+            indices.clone(),
+            context.compute(source),
+            tensor.shape()[self.axis],
+        )
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [indices, source] = tensor.arguments() else { panic!() };
+        context.append(
+            source,
+            IndexSelect::tensor(self.axis, grad.clone(), indices.clone()),
+        );
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let base = Tensor::zero([3, 2]);
+    let i = Tensor::indices([2], Arc::new(vec![2, 2]));
+    let s = Tensor::constant([2, 2], Arc::new(vec![1.0, 2.0, 3.0, 4.0]));
+    assert_eq!(
+        base.index_add(0, &i, &s).compute().unwrap().as_slice(),
+        [0.0, 0.0, 0.0, 0.0, 4.0, 6.0]
+    );
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let s = Tensor::constant([2, 3], Arc::new(vec![1.0, -2.0, 3.0, 0.5, 1.5, -1.0]));
+    let i = Tensor::indices([3], Arc::new(vec![1, 0, 1]));
+    let check = gradcheck(
+        |x| {
+            let y = IndexAdd::tensor(1, i.clone(), x[0].clone(), 2);
+            &y * &y
+        },
+        &[s],
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
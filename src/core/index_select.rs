@@ -0,0 +1,97 @@
+use crate::core::index_add::IndexAdd;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 按一维的 `indices` 选出第 `axis` 维上的若干切片
+#[derive(Debug, Copy, Clone)]
+pub struct IndexSelect {
+    axis: usize,
+}
+
+impl IndexSelect {
+    pub fn tensor(axis: usize, input: Tensor, indices: Tensor) -> Tensor {
+        let &[len] = indices.shape() else { panic!("the indices must be 1-d") };
+        assert!(indices.dtype().is_integer(), "the indices must be integers");
+        let mut shape = input.shape().to_vec();
+        shape[axis] = len;
+        let dtype = input.dtype();
+        Tensor::new_with_dtype(
+            shape,
+            vec![input, indices],
+            Box::new(IndexSelect { axis }),
+            dtype,
+        )
+    }
+
+    pub fn axis(&self) -> usize {
+        self.axis
+    }
+}
+
+impl TensorOperator for IndexSelect {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [input, indices] = tensor.arguments() else { panic!() };
+        IndexSelect::tensor(self.axis, context.compute(input), indices.clone())
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [input, indices] = tensor.arguments() else { panic!() };
+        context.append(
+            input,
+            IndexAdd::tensor(
+                self.axis,
+                indices.clone(),
+                grad.clone(),
+                input.shape()[self.axis],
+            ),
+        );
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let x = Tensor::constant([3, 2], Arc::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    let i = Tensor::indices([4], Arc::new(vec![2, 0, 2, 1]));
+    let y = x.index_select(0, &i);
+    assert_eq!(y.shape(), [4, 2]);
+    assert_eq!(
+        y.compute().unwrap().as_slice(),
+        [5.0, 6.0, 1.0, 2.0, 5.0, 6.0, 3.0, 4.0]
+    );
+    let i = Tensor::indices([1], Arc::new(vec![1]));
+    assert_eq!(
+        x.index_select(1, &i).compute().unwrap().as_slice(),
+        [2.0, 4.0, 6.0]
+    );
+
+    let i = Tensor::indices([2], Arc::new(vec![1, -1]));
+    assert_eq!(
+        x.index_select(0, &i).compute(),
+        Err(crate::cpu::CpuError::IndexOutOfBounds {
+            position: 1,
+            index: -1,
+            size: 3
+        })
+    );
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let x = Tensor::constant([3, 2], Arc::new(vec![1.0, -2.0, 3.0, 0.5, 1.5, -1.0]));
+    let i = Tensor::indices([4], Arc::new(vec![2, 0, 2, 1]));
+    let check = gradcheck(
+        |x| x[0].index_select(0, &i) * x[0].index_select(0, &i),
+        &[x],
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
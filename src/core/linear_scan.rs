@@ -0,0 +1,147 @@
+use crate::core::pad::PadMode;
+use crate::core::strided_slice::SliceSpec;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 沿第 `axis` 维的一阶线性递推 `z[i] = b[i] + c[i] * z[i - 1]`, `z[-1] = 0`
+///
+/// 不 `shifted` 时 `c[i] = a[i]`, 否则 `c[i] = a[i - 1]`. `reverse` 时从后向前递推,
+/// 上面的 `i - 1` 都换成 `i + 1`. 对 `b` 是线性的, 其转置为 `transpose`.
+#[derive(Debug, Copy, Clone)]
+pub struct LinearScan {
+    pub axis: usize,
+    pub reverse: bool,
+    pub shifted: bool,
+}
+
+impl LinearScan {
+    pub fn new(axis: usize) -> Self {
+        LinearScan {
+            axis,
+            reverse: false,
+            shifted: false,
+        }
+    }
+
+    /// 系数取前一个位置的 `a`
+    pub fn shifted(self) -> Self {
+        LinearScan {
+            shifted: true,
+            ..self
+        }
+    }
+
+    /// 从后向前递推
+    pub fn reverse(self) -> Self {
+        LinearScan {
+            reverse: true,
+            ..self
+        }
+    }
+
+    /// 固定 `a` 时关于 `b` 的转置
+    pub fn transpose(self) -> Self {
+        LinearScan {
+            reverse: !self.reverse,
+            shifted: !self.shifted,
+            ..self
+        }
+    }
+
+    /// 方向相反的递推
+    fn flipped(self) -> Self {
+        LinearScan {
+            reverse: !self.reverse,
+            ..self
+        }
+    }
+
+    pub fn apply(self, a: Tensor, b: Tensor) -> Tensor {
+        assert!(self.axis < b.shape().len());
+        assert_eq!(a.shape(), b.shape());
+        Tensor::new(b.shape().to_vec(), vec![a, b], Box::new(self))
+    }
+
+    /// 沿递推方向后移一位: 第 `i` 个元素移到 `i + 1`, 空出的位置为 0
+    pub fn shift(self, tensor: Tensor) -> Tensor {
+        let size = tensor.shape()[self.axis] as isize;
+        let mut pads = vec![[0, 0]; tensor.shape().len()];
+        let mut specs = vec![SliceSpec::all(); self.axis + 1];
+        if self.reverse {
+            pads[self.axis] = [0, 1];
+            specs[self.axis] = SliceSpec::range(1, size + 1);
+        } else {
+            pads[self.axis] = [1, 0];
+            specs[self.axis] = SliceSpec::range(0, size);
+        }
+        tensor.pad(&pads, PadMode::Constant(0.0)).slice(&specs)
+    }
+}
+
+impl TensorOperator for LinearScan {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [a, b] = tensor.arguments() else { panic!() };
+        let (da, db) = (context.compute(a), context.compute(b));
+        // dz[i] = db[i] + dc[i] * z[i - 1] + c[i] * dz[i - 1]
+        let dc = if self.shifted { self.shift(da) } else { da };
+        let grad = db + dc * self.shift(tensor.clone());
+        self.apply(a.clone(), grad)
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [a, b] = tensor.arguments() else { panic!() };
+        let grad_b = self.transpose().apply(a.clone(), grad.clone());
+        let grad_c = &grad_b * self.shift(tensor.clone());
+        let grad_a = if self.shifted {
+            self.flipped().shift(grad_c)
+        } else {
+            grad_c
+        };
+        context.append(a, grad_a);
+        context.append(b, grad_b);
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let a = Tensor::constant([2, 3], Arc::new(vec![2.0, 3.0, 4.0, 0.0, 5.0, 6.0]));
+    let b = Tensor::constant([2, 3], Arc::new(vec![1.0, 1.0, 1.0, 1.0, 2.0, 3.0]));
+    let check = |scan: LinearScan, expect: [f32; 6]| {
+        let y = scan.apply(a.clone(), b.clone());
+        assert_eq!(y.compute().unwrap().as_slice(), expect);
+    };
+    let scan = LinearScan::new(1);
+    check(scan, [1.0, 4.0, 17.0, 1.0, 7.0, 45.0]);
+    check(scan.shifted(), [1.0, 3.0, 10.0, 1.0, 2.0, 13.0]);
+    check(scan.reverse(), [9.0, 4.0, 1.0, 1.0, 17.0, 3.0]);
+    check(scan.reverse().shifted(), [16.0, 5.0, 1.0, 101.0, 20.0, 3.0]);
+    check(LinearScan::new(0), [1.0, 1.0, 1.0, 1.0, 7.0, 9.0]);
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([2, 3], Arc::new(vec![1.0, -2.0, 0.0, 0.5, 1.5, -1.0]));
+    let b = Tensor::constant([2, 3], Arc::new(vec![0.5, 1.0, -1.5, 2.0, 0.0, 1.0]));
+    let scan = LinearScan::new(1);
+    for scan in [
+        scan,
+        scan.shifted(),
+        scan.reverse(),
+        scan.reverse().shifted(),
+        LinearScan::new(0),
+    ] {
+        let f = |x: &[Tensor]| scan.apply(x[0].clone(), x[1].clone());
+        let check = gradcheck(f, &[a.clone(), b.clone()]);
+        assert!(check.max_error() < 1e-2, "{:?} {:?}", scan, check);
+    }
+}
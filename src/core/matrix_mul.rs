@@ -11,22 +11,6 @@ pub enum MatrixMul {
 }
 
 impl MatrixMul {
-    fn flip_a(self) -> Self {
-        match self {
-            MatrixMul::MulNN => MatrixMul::MulTN,
-            MatrixMul::MulNT => MatrixMul::MulTT,
-            MatrixMul::MulTN => MatrixMul::MulNN,
-            MatrixMul::MulTT => MatrixMul::MulNT,
-        }
-    }
-    fn flip_b(self) -> Self {
-        match self {
-            MatrixMul::MulNN => MatrixMul::MulNT,
-            MatrixMul::MulNT => MatrixMul::MulNN,
-            MatrixMul::MulTN => MatrixMul::MulTT,
-            MatrixMul::MulTT => MatrixMul::MulTN,
-        }
-    }
     pub fn apply(self, a: Tensor, b: Tensor) -> Tensor {
         let &[a1, a2] = a.shape() else { panic!() };
         let &[b1, b2] = b.shape() else { panic!() };
@@ -63,8 +47,27 @@ impl TensorOperator for MatrixMul {
     }
     fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
         let [a, b] = tensor.arguments() else { panic!() };
-        context.append(a, self.flip_b().apply(grad.clone(), b.clone()));
-        context.append(b, self.flip_a().apply(a.clone(), grad.clone()));
+        let (a, b, grad) = (a.clone(), b.clone(), grad.clone());
+        let (grad_a, grad_b) = match self {
+            MatrixMul::MulNN => (
+                MatrixMul::MulNT.apply(grad.clone(), b.clone()),
+                MatrixMul::MulTN.apply(a.clone(), grad),
+            ),
+            MatrixMul::MulNT => (
+                MatrixMul::MulNN.apply(grad.clone(), b.clone()),
+                MatrixMul::MulTN.apply(grad, a.clone()),
+            ),
+            MatrixMul::MulTN => (
+                MatrixMul::MulNT.apply(b.clone(), grad.clone()),
+                MatrixMul::MulNN.apply(a.clone(), grad),
+            ),
+            MatrixMul::MulTT => (
+                MatrixMul::MulTT.apply(b.clone(), grad.clone()),
+                MatrixMul::MulTT.apply(grad, a.clone()),
+            ),
+        };
+        context.append(&a, grad_a);
+        context.append(&b, grad_b);
     }
 }
 
@@ -97,3 +100,25 @@ fn test() {
             .as_slice()
     );
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([2, 3], Arc::new(vec![0.1, -0.2, 0.3, 0.4, 0.5, -0.6]));
+    let b = Tensor::constant([3, 2], Arc::new(vec![1.0, 2.0, -1.0, 0.5, 0.0, 1.5]));
+    let c = Tensor::constant([2, 3], Arc::new(vec![1.0, 0.2, -0.3, 0.7, 0.5, 0.6]));
+    for (mul, a, b) in [
+        (MatrixMul::MulNN, &a, &b),
+        (MatrixMul::MulNT, &a, &c),
+        (MatrixMul::MulTN, &a, &c),
+        (MatrixMul::MulTT, &a, &b),
+    ] {
+        let check = gradcheck(
+            |x| mul.apply(x[0].clone(), x[1].clone()),
+            &[a.clone(), b.clone()],
+        );
+        assert!(check.max_error() < 1e-2, "{:?} {:?}", mul, check);
+    }
+}
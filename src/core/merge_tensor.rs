@@ -52,3 +52,20 @@ impl TensorOperator for MergeTensor {
         }
     }
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([2, 2], Arc::new(vec![1.0, -2.0, 3.0, 0.5]));
+    let b = Tensor::constant([1, 2], Arc::new(vec![0.1, 0.2]));
+    let check = gradcheck(|x| Tensor::merge(x).powf(2.0), &[a.clone(), b.clone()]);
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+
+    let check = gradcheck(
+        |x| MergeTensor::tensor(1, x.to_vec(), vec![8]).powf(2.0),
+        &[a, b],
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
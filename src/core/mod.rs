@@ -5,21 +5,50 @@ use crate::grad::{BackwardGrad, ForwardGrad};
 use crate::tensor::Tensor;
 
 pub mod add_tensor;
+pub mod arg_sort;
 pub mod assign;
+pub mod binary;
+pub mod cast;
+pub mod categorical;
+pub mod compare;
+pub mod concat;
 pub mod constant;
+pub mod cumulative;
+pub mod custom;
 pub mod debug_assign;
+pub mod detach;
 pub mod div_tensor;
+pub mod dropout_mask;
+pub mod embedding_lookup;
 pub mod extend_scale;
+pub mod flip;
 pub mod function;
+pub mod gather;
+pub mod group_mean;
+pub mod index_add;
+pub mod index_select;
+pub mod linear_scan;
 pub mod matrix_mul;
 pub mod merge_tensor;
 pub mod mul_tensor;
+pub mod normalize;
+pub mod pad;
+pub mod pad_grad;
+pub mod random;
 pub mod reshape;
+pub mod scatter_add;
 pub mod select;
 pub mod slice_tensor;
+pub mod straight_through;
+pub mod strided_scatter;
+pub mod strided_slice;
 pub mod sub_tensor;
 pub mod sum_scale;
+pub mod tile;
+pub mod tile_sum;
+pub mod training;
 pub mod variable;
+pub mod where_tensor;
 
 pub trait TensorOperator: Any + Debug + Send + Sync {
     fn clone_box(&self) -> Box<dyn TensorOperator>;
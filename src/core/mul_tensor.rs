@@ -42,7 +42,7 @@ impl TensorOperator for MulTensor {
 
     fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
         let mut g = tensor.arguments().to_vec();
-        MulTensor::tensor(
+        AddTensor::tensor(
             tensor.shape(),
             tensor
                 .arguments()
@@ -50,7 +50,7 @@ impl TensorOperator for MulTensor {
                 .enumerate()
                 .map(|(i, x)| {
                     let back = replace(&mut g[i], context.compute(x));
-                    let p = AddTensor::tensor(tensor.shape(), g.clone());
+                    let p = MulTensor::mul(g.clone());
                     g[i] = back;
                     p
                 })
@@ -92,3 +92,15 @@ fn test() {
     assert_eq!(y.back(a).compute().unwrap().as_slice(), [2.0]);
     assert_eq!(y.back(b).compute().unwrap().as_slice(), [1.0]);
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([3], Arc::new(vec![1.0, -2.0, 0.5]));
+    let b = Tensor::constant([3], Arc::new(vec![0.3, 1.5, -1.0]));
+    let c = Tensor::constant([3], Arc::new(vec![2.0, 0.5, 1.0]));
+    let check = gradcheck(|x| MulTensor::mul(x.to_vec()), &[a, b, c]);
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
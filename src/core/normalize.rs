@@ -0,0 +1,120 @@
+use crate::core::group_mean::GroupMean;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 在 `axes` 上归一化, 分组方式同 `GroupMean`
+///
+/// `center` 时为 (x - mean) / sqrt(var + eps), 否则为 x / sqrt(mean(x^2) + eps).
+/// 方差为有偏估计.
+#[derive(Debug, Clone)]
+pub struct Normalize {
+    axes: Vec<usize>,
+    eps: f32,
+    center: bool,
+}
+
+impl Normalize {
+    pub fn tensor(input: Tensor, axes: Vec<usize>, eps: f32, center: bool) -> Tensor {
+        assert!(!axes.is_empty());
+        assert!(axes.iter().all(|&x| x < input.shape().len()));
+        assert!(eps >= 0.0);
+        Tensor::new(
+            input.shape().to_vec(),
+            vec![input],
+            Box::new(Normalize { axes, eps, center }),
+        )
+    }
+
+    pub fn axes(&self) -> &[usize] {
+        &self.axes
+    }
+
+    pub fn eps(&self) -> f32 {
+        self.eps
+    }
+
+    pub fn center(&self) -> bool {
+        self.center
+    }
+
+    /// 雅可比矩阵是对称的, 前向与反向都是 r (v - mean(v) - y mean(v y)),
+    /// 其中 r 为标准差的倒数, 不中心化时没有 mean(v) 一项.
+    fn jacobian(&self, tensor: &Tensor, v: Tensor) -> Tensor {
+        let [x] = tensor.arguments() else { panic!() };
+        let mean = |t: Tensor| GroupMean::tensor(t, self.axes.clone());
+        let (c, v) = if self.center {
+            (x - mean(x.clone()), &v - mean(v.clone()))
+        } else {
+            (x.clone(), v.clone())
+        };
+        let r = (mean(c.powf(2.0)) + self.eps).powf(-0.5);
+        r * (&v - tensor * mean(&v * tensor))
+    }
+}
+
+impl TensorOperator for Normalize {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(self.clone())
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [arg] = tensor.arguments() else { panic!() };
+        let grad = context.compute(arg);
+        self.jacobian(tensor, grad)
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [arg] = tensor.arguments() else { panic!() };
+        context.append(arg, self.jacobian(tensor, grad.clone()));
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 2], Arc::new(vec![1.0, 3.0, -2.0, 2.0]));
+    let y = x.normalize(&[1], 0.0).compute().unwrap();
+    assert_eq!(y.as_slice(), [-1.0, 1.0, -1.0, 1.0]);
+    let y = x.rms_normalize(&[0], 0.0).compute().unwrap();
+    let r = [(2.5f32).sqrt(), (6.5f32).sqrt()];
+    for (a, b) in y
+        .iter()
+        .zip([1.0 / r[0], 3.0 / r[1], -2.0 / r[0], 2.0 / r[1]])
+    {
+        assert!((a - b).abs() < 1e-6);
+    }
+
+    // 数值稳定: 大的偏移不影响结果
+    let x = Tensor::constant([3], Arc::new(vec![1e4 + 1.0, 1e4 + 2.0, 1e4 + 3.0]));
+    let y = x.normalize(&[0], 0.0).compute().unwrap();
+    let s = (1.5f32).sqrt();
+    for (a, b) in y.iter().zip([-s, 0.0, s]) {
+        assert!((a - b).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 3], Arc::new(vec![1.0, -2.0, 3.0, 0.5, 1.5, -1.0]));
+    let w = Tensor::constant([2, 3], Arc::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    for center in [true, false] {
+        for axes in [vec![0], vec![1], vec![0, 1]] {
+            let check = gradcheck(
+                |x| Normalize::tensor(x[0].clone(), axes.clone(), 1e-3, center) * &w,
+                std::slice::from_ref(&x),
+            );
+            assert!(
+                check.max_error() < 1e-2,
+                "{} {:?} {:?}",
+                center,
+                axes,
+                check
+            );
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use crate::core::pad_grad::PadGrad;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::{map_offsets, Tensor};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PadMode {
+    /// 以常数填充
+    Constant(f32),
+    /// 以边缘为轴镜像, 不重复边缘, 填充长度须小于该维长度
+    Reflect,
+    /// 重复边缘的值
+    Replicate,
+}
+
+/// 在每一维的前后各填充 `pads[axis]` 个元素
+#[derive(Debug, Clone)]
+pub struct Pad {
+    pads: Vec<[usize; 2]>,
+    mode: PadMode,
+}
+
+impl Pad {
+    pub fn tensor(input: Tensor, pads: Vec<[usize; 2]>, mode: PadMode) -> Tensor {
+        assert_eq!(pads.len(), input.shape().len());
+        for (&dim, &[before, after]) in input.shape().iter().zip(&pads) {
+            match mode {
+                PadMode::Constant(_) => {}
+                PadMode::Reflect => assert!(before < dim && after < dim),
+                PadMode::Replicate => assert!(dim > 0 || before + after == 0),
+            }
+        }
+        let shape = input
+            .shape()
+            .iter()
+            .zip(&pads)
+            .map(|(&dim, &[before, after])| before + dim + after)
+            .collect();
+        Tensor::new(shape, vec![input], Box::new(Pad { pads, mode }))
+    }
+
+    pub fn pads(&self) -> &[[usize; 2]] {
+        &self.pads
+    }
+
+    pub fn mode(&self) -> PadMode {
+        self.mode
+    }
+
+    /// 输出的每个元素对应的输入位置, 常数填充处为 `None`
+    pub fn offsets(&self, source: &[usize]) -> Vec<Option<usize>> {
+        let output = source
+            .iter()
+            .zip(&self.pads)
+            .map(|(&dim, &[before, after])| before + dim + after)
+            .collect::<Vec<_>>();
+        map_offsets(&output, source, |axis, i| {
+            let dim = source[axis] as isize;
+            let i = i as isize - self.pads[axis][0] as isize;
+            let i = match self.mode {
+                PadMode::Constant(_) if i < 0 || i >= dim => return None,
+                PadMode::Constant(_) => i,
+                PadMode::Reflect if i < 0 => -i,
+                PadMode::Reflect if i >= dim => 2 * (dim - 1) - i,
+                PadMode::Reflect => i,
+                PadMode::Replicate => i.clamp(0, dim - 1),
+            };
+            Some(i as usize)
+        })
+    }
+
+    /// 常数填充改为零, 即关于输入的导数
+    fn linear(&self) -> Pad {
+        let mode = match self.mode {
+            PadMode::Constant(_) => PadMode::Constant(0.0),
+            mode => mode,
+        };
+        Pad {
+            pads: self.pads.clone(),
+            mode,
+        }
+    }
+}
+
+impl TensorOperator for Pad {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(self.clone())
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [arg] = tensor.arguments() else { panic!() };
+        let pad = self.linear();
+        Pad::tensor(context.compute(arg), pad.pads, pad.mode)
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [arg] = tensor.arguments() else { panic!() };
+        context.append(
+            arg,
+            PadGrad::tensor(grad.clone(), self.linear(), arg.shape().to_vec()),
+        );
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 3], Arc::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    let pads = [[0, 1], [2, 1]];
+    let check = |mode: PadMode, expect: &[f32]| {
+        let y = x.pad(&pads, mode);
+        assert_eq!(y.shape(), [3, 6]);
+        assert_eq!(y.compute().unwrap().as_slice(), expect);
+    };
+    #[rustfmt::skip]
+    check(PadMode::Constant(-1.0), &[
+        -1.0, -1.0, 1.0, 2.0, 3.0, -1.0,
+        -1.0, -1.0, 4.0, 5.0, 6.0, -1.0,
+        -1.0, -1.0, -1.0, -1.0, -1.0, -1.0,
+    ]);
+    #[rustfmt::skip]
+    check(PadMode::Reflect, &[
+        3.0, 2.0, 1.0, 2.0, 3.0, 2.0,
+        6.0, 5.0, 4.0, 5.0, 6.0, 5.0,
+        3.0, 2.0, 1.0, 2.0, 3.0, 2.0,
+    ]);
+    #[rustfmt::skip]
+    check(PadMode::Replicate, &[
+        1.0, 1.0, 1.0, 2.0, 3.0, 3.0,
+        4.0, 4.0, 4.0, 5.0, 6.0, 6.0,
+        4.0, 4.0, 4.0, 5.0, 6.0, 6.0,
+    ]);
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 3], Arc::new(vec![1.0, -2.0, 3.0, 0.5, 1.5, -1.0]));
+    for mode in [PadMode::Constant(2.0), PadMode::Reflect, PadMode::Replicate] {
+        let check = gradcheck(
+            |x| {
+                let y = x[0].pad(&[[1, 1], [2, 1]], mode);
+                &y * &y
+            },
+            std::slice::from_ref(&x),
+        );
+        assert!(check.max_error() < 1e-2, "{:?} {:?}", mode, check);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::core::pad::Pad;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// `Pad` 的伴随: 把填充后的张量按来源累加回原形状
+#[derive(Debug, Clone)]
+pub struct PadGrad {
+    pad: Pad,
+}
+
+impl PadGrad {
+    pub fn tensor(input: Tensor, pad: Pad, shape: Vec<usize>) -> Tensor {
+        Tensor::new(shape, vec![input], Box::new(PadGrad { pad }))
+    }
+
+    pub fn pad(&self) -> &Pad {
+        &self.pad
+    }
+}
+
+impl TensorOperator for PadGrad {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(self.clone())
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [arg] = tensor.arguments() else { panic!() };
+        PadGrad::tensor(
+            context.compute(arg),
+            self.pad.clone(),
+            tensor.shape().to_vec(),
+        )
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [arg] = tensor.arguments() else { panic!() };
+        context.append(
+            arg,
+            Pad::tensor(grad.clone(), self.pad.pads().to_vec(), self.pad.mode()),
+        );
+    }
+}
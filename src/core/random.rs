@@ -0,0 +1,107 @@
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Distribution {
+    /// [low, high) 上的均匀分布
+    Uniform(f32, f32),
+    /// 均值和标准差
+    Normal(f32, f32),
+    /// 以概率 p 为 1, 否则为 0
+    Bernoulli(f32),
+}
+
+/// 随机张量, 每次由后端计算时重新抽取
+///
+/// 有 `seed` 时每次计算都得到相同的结果, 否则使用后端的随机数生成器.
+#[derive(Debug, Copy, Clone)]
+pub struct Random {
+    distribution: Distribution,
+    seed: Option<u64>,
+}
+
+impl Random {
+    pub fn tensor<S: AsRef<[usize]>>(
+        shape: S,
+        distribution: Distribution,
+        seed: Option<u64>,
+    ) -> Tensor {
+        match distribution {
+            Distribution::Uniform(low, high) => assert!(low < high),
+            Distribution::Normal(_, std) => assert!(std >= 0.0),
+            Distribution::Bernoulli(p) => assert!((0.0..=1.0).contains(&p)),
+        }
+        Tensor::new(
+            shape.as_ref().to_vec(),
+            vec![],
+            Box::new(Random { distribution, seed }),
+        )
+    }
+
+    pub fn distribution(&self) -> Distribution {
+        self.distribution
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+}
+
+impl TensorOperator for Random {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, _context: &mut ForwardGrad) -> Tensor {
+        Tensor::zero(tensor.shape())
+    }
+
+    fn backward_grad(&self, _tensor: &Tensor, _grad: &Tensor, _context: &mut BackwardGrad) {}
+}
+
+#[test]
+fn test() {
+    use crate::cpu::CpuContext;
+
+    let mut context = CpuContext::new();
+    context.set_seed(1);
+    let mean = |x: &[f32]| x.iter().sum::<f32>() / x.len() as f32;
+
+    let x = Tensor::uniform([1000], -1.0, 3.0, None);
+    let value = context.compute(&x).unwrap();
+    assert!(value.iter().all(|x| (-1.0..3.0).contains(x)));
+    assert!((mean(&value) - 1.0).abs() < 0.2);
+
+    let x = Tensor::normal([1000], 2.0, 0.5, None);
+    let value = context.compute(&x).unwrap();
+    assert!((mean(&value) - 2.0).abs() < 0.1);
+
+    let x = Tensor::bernoulli([1000], 0.3, None);
+    let value = context.compute(&x).unwrap();
+    assert!(value.iter().all(|&x| x == 0.0 || x == 1.0));
+    assert!((mean(&value) - 0.3).abs() < 0.1);
+
+    // 有种子时与上下文无关
+    let x = Tensor::normal([4], 0.0, 1.0, Some(3));
+    assert_eq!(x.compute().unwrap(), x.compute().unwrap());
+    let y = Tensor::normal([4], 0.0, 1.0, None);
+    assert_ne!(y.compute().unwrap(), y.compute().unwrap());
+}
+
+#[test]
+fn sample_normal() {
+    use std::sync::Arc;
+
+    let mean = Tensor::constant([3], Arc::new(vec![1.0, 2.0, 3.0]));
+    let std = Tensor::constant([3], Arc::new(vec![0.5, 1.0, 2.0]));
+    let x = Tensor::sample_normal(&mean, &std, Some(9));
+    let eps = Tensor::normal([3], 0.0, 1.0, Some(9)).compute().unwrap();
+    let value = x.compute().unwrap();
+    for i in 0..3 {
+        let expect = [1.0, 2.0, 3.0][i] + [0.5, 1.0, 2.0][i] * eps[i];
+        assert!((value[i] - expect).abs() < 1e-6);
+    }
+    assert_eq!(x.back(&mean).compute().unwrap().as_slice(), [1.0; 3]);
+    assert_eq!(x.back(&std).compute().unwrap(), eps);
+}
@@ -27,3 +27,16 @@ impl TensorOperator for Reshape {
         context.append(arg, Reshape::reshape(grad.clone(), arg.shape().to_vec()));
     }
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([2, 3], Arc::new(vec![1.0, -2.0, 0.5, 0.3, 1.5, -1.0]));
+    let check = gradcheck(
+        |x| x[0].reshape([3, 2]).matrix_mul(x[0].reshape([2, 3])),
+        &[a],
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
@@ -0,0 +1,83 @@
+use crate::core::gather::{check_shape, Gather};
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// `Gather` 的伴随: 从零开始, `output[.., indices[.., i, ..], ..] += source[.., i, ..]`
+#[derive(Debug, Copy, Clone)]
+pub struct ScatterAdd {
+    axis: usize,
+}
+
+impl ScatterAdd {
+    pub fn tensor(axis: usize, indices: Tensor, source: Tensor, shape: Vec<usize>) -> Tensor {
+        assert_eq!(indices.shape(), source.shape());
+        assert!(indices.dtype().is_integer(), "the indices must be integers");
+        check_shape(axis, &shape, source.shape());
+        let dtype = source.dtype();
+        Tensor::new_with_dtype(
+            shape,
+            vec![indices, source],
+            Box::new(ScatterAdd { axis }),
+            dtype,
+        )
+    }
+
+    pub fn axis(&self) -> usize {
+        self.axis
+    }
+}
+
+impl TensorOperator for ScatterAdd {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [indices, source] = tensor.arguments() else { panic!() };
+        ScatterAdd::tensor(
+            self.axis,
+            indices.clone(),
+            context.compute(source),
+            tensor.shape().to_vec(),
+        )
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [indices, source] = tensor.arguments() else { panic!() };
+        context.append(
+            source,
+            Gather::tensor(self.axis, grad.clone(), indices.clone()),
+        );
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let base = Tensor::constant([2, 2], Arc::new(vec![1.0; 4]));
+    let i = Tensor::indices([2, 3], Arc::new(vec![0, 1, 0, 1, 1, 1]));
+    let s = Tensor::constant([2, 3], Arc::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    assert_eq!(
+        base.scatter_add(1, &i, &s).compute().unwrap().as_slice(),
+        [5.0, 3.0, 1.0, 16.0]
+    );
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let s = Tensor::constant([3, 2], Arc::new(vec![1.0, -2.0, 3.0, 0.5, 1.5, -1.0]));
+    let i = Tensor::indices([3, 2], Arc::new(vec![0, 1, 0, 0, 1, 0]));
+    let check = gradcheck(
+        |x| {
+            let y = ScatterAdd::tensor(0, i.clone(), x[0].clone(), vec![2, 2]);
+            &y * &y
+        },
+        &[s],
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
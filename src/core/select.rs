@@ -50,3 +50,19 @@ impl TensorOperator for Select {
         Ok(())
     }
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([2], Arc::new(vec![1.0, -2.0]));
+    let b = Tensor::constant([2], Arc::new(vec![0.3, 1.5]));
+    for cond in [1.0, -1.0] {
+        let check = gradcheck(
+            |x| Tensor::scale(cond).select(&x[0], &x[1]) * &x[0],
+            &[a.clone(), b.clone()],
+        );
+        assert!(check.max_error() < 1e-2, "{:?}", check);
+    }
+}
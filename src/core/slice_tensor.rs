@@ -57,3 +57,16 @@ fn test() {
         [0.0, 0.0, 0.0, 1.0, 1.0, 1.0]
     )
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([3, 2], Arc::new(vec![1.0, -2.0, 0.5, 0.3, 1.5, -1.0]));
+    let check = gradcheck(
+        |x| x[0].get([1]) * SliceTensor::slice(x[0].clone(), 1, 2).get([1]),
+        &[a],
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
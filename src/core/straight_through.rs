@@ -0,0 +1,47 @@
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 值取自第一个参数, 梯度按第二个参数求
+///
+/// 例如 `x.apply(Function::Step).straight_through(&x)` 前向为阶跃函数,
+/// 反向按恒等函数传递梯度.
+#[derive(Debug, Copy, Clone)]
+pub struct StraightThrough;
+
+impl StraightThrough {
+    pub fn tensor(value: Tensor, grad: Tensor) -> Tensor {
+        assert_eq!(value.shape(), grad.shape());
+        Tensor::new(
+            value.shape().to_vec(),
+            vec![value, grad],
+            Box::new(StraightThrough),
+        )
+    }
+}
+
+impl TensorOperator for StraightThrough {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [_, grad] = tensor.arguments() else { panic!() };
+        context.compute(grad)
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [_, surrogate] = tensor.arguments() else { panic!() };
+        context.append(surrogate, grad);
+    }
+}
+
+#[test]
+fn test() {
+    use crate::core::function::Function;
+
+    let a = Tensor::scale(-2.0);
+    let y = a.apply(Function::Step).straight_through(&a * 3.0);
+    assert_eq!(y.compute().unwrap().as_slice(), [0.0]);
+    assert_eq!(y.back(&a).compute().unwrap().as_slice(), [3.0]);
+}
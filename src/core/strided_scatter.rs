@@ -0,0 +1,49 @@
+use crate::core::strided_slice::StridedSlice;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// `StridedSlice` 的伴随: 把输入放回形状为 `shape` 的零张量中被切出的位置
+#[derive(Debug, Clone)]
+pub struct StridedScatter {
+    slice: StridedSlice,
+}
+
+impl StridedScatter {
+    pub fn tensor(input: Tensor, slice: StridedSlice, shape: Vec<usize>) -> Tensor {
+        assert_eq!(input.shape().len(), shape.len());
+        Tensor::new(shape, vec![input], Box::new(StridedScatter { slice }))
+    }
+
+    pub fn slice(&self) -> &StridedSlice {
+        &self.slice
+    }
+}
+
+impl TensorOperator for StridedScatter {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(self.clone())
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [arg] = tensor.arguments() else { panic!() };
+        StridedScatter::tensor(
+            context.compute(arg),
+            self.slice.clone(),
+            tensor.shape().to_vec(),
+        )
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [arg] = tensor.arguments() else { panic!() };
+        context.append(
+            arg,
+            StridedSlice::tensor(
+                grad.clone(),
+                self.slice.begin().to_vec(),
+                self.slice.step().to_vec(),
+                arg.shape().to_vec(),
+            ),
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use crate::core::strided_scatter::StridedScatter;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// `Tensor::slice` 中一个维度的取法, 负数从末尾开始计
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SliceSpec {
+    /// 取一个下标, 并去掉该维
+    Index(isize),
+    /// 与 Python 的 `start:end:step` 相同, `None` 取决于 `step` 的方向
+    Range {
+        start: Option<isize>,
+        end: Option<isize>,
+        step: isize,
+    },
+    /// 插入长度为 1 的新维, 不消耗输入的维度
+    NewAxis,
+}
+
+impl SliceSpec {
+    pub fn all() -> Self {
+        SliceSpec::Range {
+            start: None,
+            end: None,
+            step: 1,
+        }
+    }
+
+    pub fn range(start: isize, end: isize) -> Self {
+        SliceSpec::Range {
+            start: Some(start),
+            end: Some(end),
+            step: 1,
+        }
+    }
+
+    pub fn step(step: isize) -> Self {
+        SliceSpec::Range {
+            start: None,
+            end: None,
+            step,
+        }
+    }
+
+    /// 在长度为 `dim` 的维上解析为 (起点, 长度, 步长)
+    fn resolve(self, dim: usize) -> (usize, usize, isize) {
+        let dim = dim as isize;
+        match self {
+            SliceSpec::Index(index) => {
+                let i = if index < 0 { index + dim } else { index };
+                assert!(
+                    0 <= i && i < dim,
+                    "the index {} is out of bounds for size {}",
+                    index,
+                    dim
+                );
+                (i as usize, 1, 1)
+            }
+            SliceSpec::Range { start, end, step } => {
+                assert_ne!(step, 0);
+                let norm = |x: isize| if x < 0 { x + dim } else { x };
+                if step > 0 {
+                    let start = start.map_or(0, norm).clamp(0, dim);
+                    let end = end.map_or(dim, norm).clamp(0, dim);
+                    let len = if end > start {
+                        (end - start + step - 1) / step
+                    } else {
+                        0
+                    };
+                    (start as usize, len as usize, step)
+                } else {
+                    let start = start.map_or(dim - 1, norm).clamp(-1, dim - 1);
+                    let end = end.map_or(-1, norm).clamp(-1, dim - 1);
+                    let len = if start > end {
+                        (start - end - step - 1) / -step
+                    } else {
+                        0
+                    };
+                    (start.max(0) as usize, len as usize, step)
+                }
+            }
+            SliceSpec::NewAxis => unreachable!(),
+        }
+    }
+}
+
+/// 各维按 `begin + k * step` 取出, 输出与输入维数相同
+#[derive(Debug, Clone)]
+pub struct StridedSlice {
+    begin: Vec<usize>,
+    step: Vec<isize>,
+}
+
+impl StridedSlice {
+    pub fn tensor(input: Tensor, begin: Vec<usize>, step: Vec<isize>, shape: Vec<usize>) -> Tensor {
+        assert_eq!(begin.len(), input.shape().len());
+        assert_eq!(step.len(), input.shape().len());
+        assert_eq!(shape.len(), input.shape().len());
+        Tensor::new(shape, vec![input], Box::new(StridedSlice { begin, step }))
+    }
+
+    /// 按 `specs` 切片, 未给出的维度取全部
+    pub fn slice(input: Tensor, specs: &[SliceSpec]) -> Tensor {
+        let mut begin = Vec::new();
+        let mut step = Vec::new();
+        let mut shape = Vec::new();
+        let mut output = Vec::new();
+        let mut axis = 0;
+        for &spec in specs {
+            if spec == SliceSpec::NewAxis {
+                output.push(1);
+                continue;
+            }
+            assert!(axis < input.shape().len(), "too many slice specs");
+            let (b, len, s) = spec.resolve(input.shape()[axis]);
+            begin.push(b);
+            step.push(s);
+            shape.push(len);
+            if !matches!(spec, SliceSpec::Index(_)) {
+                output.push(len);
+            }
+            axis += 1;
+        }
+        for &dim in &input.shape()[axis..] {
+            begin.push(0);
+            step.push(1);
+            shape.push(dim);
+            output.push(dim);
+        }
+        StridedSlice::tensor(input, begin, step, shape).reshape(output)
+    }
+
+    pub fn begin(&self) -> &[usize] {
+        &self.begin
+    }
+
+    pub fn step(&self) -> &[isize] {
+        &self.step
+    }
+
+    /// 输出的每个元素在输入中的位置, `source` 为输入形状
+    pub fn offsets(&self, source: &[usize], shape: &[usize]) -> Vec<usize> {
+        let mut offsets = vec![0];
+        for axis in 0..source.len() {
+            let mut next = Vec::with_capacity(offsets.len() * shape[axis]);
+            for &o in &offsets {
+                for k in 0..shape[axis] {
+                    let i = self.begin[axis] as isize + k as isize * self.step[axis];
+                    next.push(o * source[axis] + i as usize);
+                }
+            }
+            offsets = next;
+        }
+        offsets
+    }
+}
+
+impl TensorOperator for StridedSlice {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(self.clone())
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [arg] = tensor.arguments() else { panic!() };
+        StridedSlice::tensor(
+            context.compute(arg),
+            self.begin.clone(),
+            self.step.clone(),
+            tensor.shape().to_vec(),
+        )
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [arg] = tensor.arguments() else { panic!() };
+        context.append(
+            arg,
+            StridedScatter::tensor(grad.clone(), self.clone(), arg.shape().to_vec()),
+        );
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+    use SliceSpec::*;
+
+    let x = Tensor::constant([3, 4], Arc::new((0..12).map(|x| x as f32).collect()));
+    let check = |specs: &[SliceSpec], shape: &[usize], expect: &[f32]| {
+        let y = x.slice(specs);
+        assert_eq!(y.shape(), shape);
+        assert_eq!(y.compute().unwrap().as_slice(), expect);
+    };
+    check(&[Index(1)], &[4], &[4.0, 5.0, 6.0, 7.0]);
+    check(&[SliceSpec::all(), Index(-1)], &[3], &[3.0, 7.0, 11.0]);
+    check(
+        &[SliceSpec::range(0, 2), SliceSpec::step(2)],
+        &[2, 2],
+        &[0.0, 2.0, 4.0, 6.0],
+    );
+    check(
+        &[SliceSpec::step(-1), SliceSpec::range(-2, 4)],
+        &[3, 2],
+        &[10.0, 11.0, 6.0, 7.0, 2.0, 3.0],
+    );
+    check(
+        &[
+            Index(0),
+            Range {
+                start: Some(3),
+                end: None,
+                step: -2,
+            },
+        ],
+        &[2],
+        &[3.0, 1.0],
+    );
+    check(
+        &[NewAxis, Index(2), NewAxis],
+        &[1, 1, 4],
+        &[8.0, 9.0, 10.0, 11.0],
+    );
+    check(&[SliceSpec::range(2, 1)], &[0, 4], &[]);
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let x = Tensor::constant(
+        [3, 4],
+        Arc::new((0..12).map(|x| x as f32 * 0.3 - 1.0).collect()),
+    );
+    let specs = [SliceSpec::step(-2), SliceSpec::range(1, -1)];
+    let check = gradcheck(|x| x[0].slice(&specs) * x[0].slice(&specs), &[x]);
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
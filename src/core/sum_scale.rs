@@ -24,7 +24,7 @@ impl TensorOperator for SumScale {
 
     fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
         let [arg] = tensor.arguments() else { panic!() };
-        SumScale::sum(context.compute(arg))
+        SumScale::sum_to_shape(context.compute(arg), tensor.shape().to_vec())
     }
 
     fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
@@ -45,3 +45,19 @@ fn test() {
         [1.0, 1.0, 1.0, 1.0, 1.0, 1.0]
     )
 }
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([2, 2], Arc::new(vec![1.0, -2.0, 0.5, 0.3]));
+    let check = gradcheck(
+        |x| {
+            SumScale::sum_to_shape(x[0].powf(2.0), vec![1, 1])
+                * SumScale::sum_to_shape(x[0].clone(), vec![1, 1])
+        },
+        &[a],
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
@@ -0,0 +1,91 @@
+use crate::core::tile_sum::TileSum;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::{map_offsets, Tensor};
+
+/// 把整个张量在第 i 维上重复 `repeats[i]` 次
+#[derive(Debug, Clone)]
+pub struct Tile {
+    repeats: Vec<usize>,
+}
+
+impl Tile {
+    pub fn tensor(input: Tensor, repeats: Vec<usize>) -> Tensor {
+        assert_eq!(repeats.len(), input.shape().len());
+        let shape = input
+            .shape()
+            .iter()
+            .zip(&repeats)
+            .map(|(a, b)| a * b)
+            .collect();
+        Tensor::new(shape, vec![input], Box::new(Tile { repeats }))
+    }
+
+    pub fn repeats(&self) -> &[usize] {
+        &self.repeats
+    }
+
+    /// 输出的每个元素对应的输入位置
+    pub fn offsets(output: &[usize], source: &[usize]) -> Vec<usize> {
+        map_offsets(output, source, |axis, i| Some(i % source[axis]))
+            .into_iter()
+            .map(Option::unwrap)
+            .collect()
+    }
+}
+
+impl TensorOperator for Tile {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(self.clone())
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [arg] = tensor.arguments() else { panic!() };
+        Tile::tensor(context.compute(arg), self.repeats.clone())
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [arg] = tensor.arguments() else { panic!() };
+        context.append(arg, TileSum::tensor(grad.clone(), self.repeats.clone()));
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 2], Arc::new(vec![1.0, 2.0, 3.0, 4.0]));
+    let y = x.tile(&[1, 2]);
+    assert_eq!(y.shape(), [2, 4]);
+    assert_eq!(
+        y.compute().unwrap().as_slice(),
+        [1.0, 2.0, 1.0, 2.0, 3.0, 4.0, 3.0, 4.0]
+    );
+    let y = x.repeat(2, 1);
+    assert_eq!(y.shape(), [2, 4]);
+    assert_eq!(
+        y.compute().unwrap().as_slice(),
+        [1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0]
+    );
+    let y = x.repeat(2, 0);
+    assert_eq!(
+        y.compute().unwrap().as_slice(),
+        [1.0, 2.0, 1.0, 2.0, 3.0, 4.0, 3.0, 4.0]
+    );
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let x = Tensor::constant([2, 3], Arc::new(vec![1.0, -2.0, 3.0, 0.5, 1.5, -1.0]));
+    let check = gradcheck(
+        |x| {
+            let y = x[0].tile(&[2, 3]);
+            &y * &y
+        },
+        &[x],
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
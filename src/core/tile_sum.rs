@@ -0,0 +1,42 @@
+use crate::core::tile::Tile;
+use crate::core::TensorOperator;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// `Tile` 的伴随: 把各次重复累加为一份
+#[derive(Debug, Clone)]
+pub struct TileSum {
+    repeats: Vec<usize>,
+}
+
+impl TileSum {
+    pub fn tensor(input: Tensor, repeats: Vec<usize>) -> Tensor {
+        assert_eq!(repeats.len(), input.shape().len());
+        let shape = input
+            .shape()
+            .iter()
+            .zip(&repeats)
+            .map(|(&a, &b)| {
+                assert_eq!(a % b, 0);
+                a / b
+            })
+            .collect();
+        Tensor::new(shape, vec![input], Box::new(TileSum { repeats }))
+    }
+}
+
+impl TensorOperator for TileSum {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(self.clone())
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [arg] = tensor.arguments() else { panic!() };
+        TileSum::tensor(context.compute(arg), self.repeats.clone())
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [arg] = tensor.arguments() else { panic!() };
+        context.append(arg, Tile::tensor(grad.clone(), self.repeats.clone()));
+    }
+}
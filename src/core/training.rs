@@ -0,0 +1,28 @@
+use crate::core::TensorOperator;
+use crate::dtype::DType;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 后端是否处于训练模式的标量, 是为 1, 否则为 0
+///
+/// 与 `Select` 一起使用, 使只在训练时生效的层由后端的训练模式统一控制.
+#[derive(Debug, Copy, Clone)]
+pub struct Training;
+
+impl Training {
+    pub fn tensor() -> Tensor {
+        Tensor::new_with_dtype(vec![], vec![], Box::new(Training), DType::Bool)
+    }
+}
+
+impl TensorOperator for Training {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, _context: &mut ForwardGrad) -> Tensor {
+        Tensor::zero(tensor.shape())
+    }
+
+    fn backward_grad(&self, _tensor: &Tensor, _grad: &Tensor, _context: &mut BackwardGrad) {}
+}
@@ -0,0 +1,87 @@
+use std::fmt::{Display, Formatter};
+
+use crate::core::TensorOperator;
+use crate::dtype::DType;
+use crate::grad::{BackwardGrad, ForwardGrad};
+use crate::tensor::Tensor;
+
+/// 逐元素选择, `mask` 大于零处取 `pos`, 否则取 `neg`
+#[derive(Debug, Copy, Clone)]
+pub struct WhereTensor;
+
+impl WhereTensor {
+    pub fn tensor(mask: Tensor, pos: Tensor, neg: Tensor) -> Tensor {
+        assert_eq!(mask.shape(), pos.shape());
+        assert_eq!(pos.shape(), neg.shape());
+        let dtype = DType::promote([pos.dtype(), neg.dtype()]).unwrap();
+        Tensor::new_with_dtype(
+            pos.shape().to_vec(),
+            vec![mask, pos, neg],
+            Box::new(WhereTensor),
+            dtype,
+        )
+    }
+}
+
+impl TensorOperator for WhereTensor {
+    fn clone_box(&self) -> Box<dyn TensorOperator> {
+        Box::new(*self)
+    }
+
+    fn forward_grad(&self, tensor: &Tensor, context: &mut ForwardGrad) -> Tensor {
+        let [mask, pos, neg] = tensor.arguments() else { panic!() };
+        WhereTensor::tensor(mask.clone(), context.compute(pos), context.compute(neg))
+    }
+
+    fn backward_grad(&self, tensor: &Tensor, grad: &Tensor, context: &mut BackwardGrad) {
+        let [mask, pos, neg] = tensor.arguments() else { panic!() };
+        let zero = Tensor::zero(tensor.shape());
+        context.append(
+            pos,
+            WhereTensor::tensor(mask.clone(), grad.clone(), zero.clone()),
+        );
+        context.append(neg, WhereTensor::tensor(mask.clone(), zero, grad.clone()));
+    }
+
+    fn display(&self, tensor: &Tensor, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let [mask, pos, neg] = tensor.arguments() else { panic!() };
+        f.write_str("where(")?;
+        Display::fmt(mask, f)?;
+        f.write_str(", ")?;
+        Display::fmt(pos, f)?;
+        f.write_str(", ")?;
+        Display::fmt(neg, f)?;
+        f.write_str(")")?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let x = Tensor::constant([4], Arc::new(vec![-2.0, -0.5, 0.5, 2.0]));
+    let y = x.gt(Tensor::scale(0.0)).select_where(&x, x.powf(2.0));
+    assert_eq!(y.compute().unwrap().as_slice(), [4.0, 0.25, 0.5, 2.0]);
+
+    // 标量会扩展为另一方的形状
+    let y = x
+        .lt(Tensor::scale(0.0))
+        .select_where(Tensor::scale(0.0), &x);
+    assert_eq!(y.compute().unwrap().as_slice(), [0.0, 0.0, 0.5, 2.0]);
+    assert_eq!(y.dtype(), DType::F32);
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+    use std::sync::Arc;
+
+    let a = Tensor::constant([4], Arc::new(vec![-2.0, -0.5, 0.5, 2.0]));
+    let b = Tensor::constant([4], Arc::new(vec![0.3, 1.5, -1.0, 0.7]));
+    let check = gradcheck(
+        |x| x[0].ge(&x[1]).select_where(&x[0] * &x[1], x[1].powf(3.0)),
+        &[a, b],
+    );
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
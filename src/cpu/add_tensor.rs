@@ -1,15 +1,16 @@
 use std::sync::Arc;
 
 use crate::core::add_tensor::AddTensor;
-use crate::cpu::{CpuContext, CpuOperator};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
 use crate::tensor::{data_size, Tensor};
 
-impl CpuOperator for AddTensor {
-    fn compute(&self, tensor: &Tensor, context: &mut CpuContext) -> Result<Arc<Vec<f32>>, ()> {
+impl<T: Element> CpuOperator<T> for AddTensor {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
         let len = data_size(tensor.shape());
 
         if tensor.arguments().len() == 0 {
-            return Ok(Arc::new(vec![0.0; len]));
+            return Ok(Arc::new(vec![T::ZERO; len]));
         }
         if tensor.arguments().len() == 1 {
             return context.compute(&tensor.arguments()[0]);
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::core::arg_sort::ArgSort;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{axis_split, Tensor};
+
+impl<T: Element> CpuOperator<T> for ArgSort {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [arg] = tensor.arguments() else { panic!() };
+        let (outer, size, inner) = axis_split(arg.shape(), self.axis());
+        let input = context.compute(arg)?;
+
+        let k = self.k();
+        let mut output = vec![T::ZERO; outer * k * inner];
+        let mut order = Vec::with_capacity(size);
+        for o in 0..outer {
+            for n in 0..inner {
+                let at = |i: usize| input[(o * size + i) * inner + n];
+                order.clear();
+                order.extend(0..size);
+                order.sort_by(|&a, &b| {
+                    let c = at(a).total_cmp(&at(b));
+                    if self.descending() {
+                        c.reverse()
+                    } else {
+                        c
+                    }
+                });
+                for (i, &index) in order.iter().take(k).enumerate() {
+                    output[(o * k + i) * inner + n] = T::from_f64(index as f64);
+                }
+            }
+        }
+        Ok(Arc::new(output))
+    }
+}
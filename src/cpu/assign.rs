@@ -1,11 +1,10 @@
-use std::sync::Arc;
-
 use crate::core::assign::Assign;
-use crate::cpu::{CpuContext, CpuOperator};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
 use crate::tensor::Tensor;
 
-impl CpuOperator for Assign {
-    fn compute(&self, tensor: &Tensor, context: &mut CpuContext) -> Result<Arc<Vec<f32>>, ()> {
+impl<T: Element> CpuOperator<T> for Assign {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
         let [arg] = tensor.arguments() else { panic!() };
         context.compute(arg)
     }
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::core::binary::Binary;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::Tensor;
+
+impl<T: Element> CpuOperator<T> for Binary {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [a, b] = tensor.arguments() else { panic!() };
+        let a = context.compute(a)?;
+        let b = context.compute(b)?;
+        assert_eq!(a.len(), b.len());
+        let f = match self {
+            Binary::Maximum => |a: T, b| if a >= b { a } else { b },
+            Binary::Minimum => |a: T, b| if a <= b { a } else { b },
+            Binary::Pow => T::powf,
+            Binary::Atan2 => T::atan2,
+            Binary::Hypot => T::hypot,
+        };
+        Ok(Arc::new(
+            a.iter().zip(b.iter()).map(|(&a, &b)| f(a, b)).collect(),
+        ))
+    }
+}
@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+use crate::core::cast::Cast;
+use crate::cpu::{conform, CpuContext, CpuOperator, CpuResult};
+use crate::dtype::DType;
+use crate::element::Element;
+use crate::tensor::Tensor;
+
+impl<T: Element> CpuOperator<T> for Cast {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [arg] = tensor.arguments() else { panic!() };
+        let input = context.compute(arg)?;
+        if self.dtype() == DType::F32 {
+            return Ok(Arc::new(
+                input.iter().map(|&x| T::from_f32(x.to_f32())).collect(),
+            ));
+        }
+        Ok(conform(self.dtype(), input))
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use crate::core::categorical::Categorical;
+use crate::cpu::{CpuContext, CpuError, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::Tensor;
+
+impl<T: Element> CpuOperator<T> for Categorical {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [arg] = tensor.arguments() else { panic!() };
+        let &classes = arg.shape().last().unwrap();
+        let weights = context.compute(arg)?;
+        let mut seeded;
+        let rng = match self.seed() {
+            Some(seed) => {
+                seeded = SmallRng::seed_from_u64(seed);
+                &mut seeded
+            }
+            None => context.rng(),
+        };
+        let output = weights
+            .chunks(classes)
+            .enumerate()
+            .map(|(i, row)| {
+                let total = row.iter().map(|x| x.to_f64()).sum::<f64>();
+                let valid = row.iter().all(|&x| x >= T::ZERO) && total > 0.0 && total.is_finite();
+                if !valid {
+                    return Err(CpuError::InvalidWeights { row: i });
+                }
+                let mut u = rng.gen::<f64>() * total;
+                // 跳过权重为零的类别, 舍入误差时取最后一个权重非零的类别
+                let mut index = 0;
+                for (i, &x) in row.iter().enumerate() {
+                    if x > T::ZERO {
+                        index = i;
+                        u -= x.to_f64();
+                        if u < 0.0 {
+                            break;
+                        }
+                    }
+                }
+                Ok(T::from_f64(index as f64))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Arc::new(output))
+    }
+}
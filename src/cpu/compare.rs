@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::core::compare::Compare;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::Tensor;
+
+impl<T: Element> CpuOperator<T> for Compare {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [a, b] = tensor.arguments() else { panic!() };
+        let a = context.compute(a)?;
+        let b = context.compute(b)?;
+        assert_eq!(a.len(), b.len());
+        let output = a
+            .iter()
+            .zip(b.iter())
+            .map(|(a, b)| {
+                let r = match self {
+                    Compare::Gt => a > b,
+                    Compare::Lt => a < b,
+                    Compare::Ge => a >= b,
+                    Compare::Le => a <= b,
+                    Compare::Eq => a == b,
+                };
+                if r {
+                    T::ONE
+                } else {
+                    T::ZERO
+                }
+            })
+            .collect();
+        Ok(Arc::new(output))
+    }
+}
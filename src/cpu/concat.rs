@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use crate::core::concat::Concat;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{axis_split, data_size, Tensor};
+
+impl<T: Element> CpuOperator<T> for Concat {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let (outer, _, inner) = axis_split(tensor.shape(), self.axis());
+        let mut input = Vec::with_capacity(tensor.arguments().len());
+        for arg in tensor.arguments() {
+            input.push((context.compute(arg)?, arg.shape()[self.axis()] * inner));
+        }
+
+        let mut output = Vec::with_capacity(data_size(tensor.shape()));
+        for o in 0..outer {
+            for (data, len) in &input {
+                output.extend_from_slice(&data[o * len..(o + 1) * len]);
+            }
+        }
+        Ok(Arc::new(output))
+    }
+}
@@ -1,11 +1,10 @@
-use std::sync::Arc;
-
 use crate::core::constant::Constant;
-use crate::cpu::{CpuContext, CpuOperator};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
 use crate::tensor::Tensor;
 
-impl CpuOperator for Constant {
-    fn compute(&self, _tensor: &Tensor, _context: &mut CpuContext) -> Result<Arc<Vec<f32>>, ()> {
-        Ok(self.data().clone())
+impl<T: Element> CpuOperator<T> for Constant {
+    fn compute(&self, _tensor: &Tensor, _context: &mut CpuContext<T>) -> CpuResult<T> {
+        Ok(self.data_as())
     }
 }
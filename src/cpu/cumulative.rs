@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::core::cumulative::{Cumulative, Scan};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{axis_split, Tensor};
+
+impl<T: Element> CpuOperator<T> for Cumulative {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [arg] = tensor.arguments() else { panic!() };
+        let (outer, size, inner) = axis_split(arg.shape(), self.axis);
+        let input = context.compute(arg)?;
+
+        let (init, op): (T, fn(T, T) -> T) = match self.scan {
+            Scan::Sum => (T::ZERO, |a, b| a + b),
+            Scan::Prod => (T::ONE, |a, b| a * b),
+        };
+        let mut output = vec![T::ZERO; input.len()];
+        for o in 0..outer {
+            for n in 0..inner {
+                let mut acc = init;
+                for i in 0..size {
+                    let i = if self.reverse { size - 1 - i } else { i };
+                    let position = (o * size + i) * inner + n;
+                    if self.exclusive {
+                        output[position] = acc;
+                        acc = op(acc, input[position]);
+                    } else {
+                        acc = op(acc, input[position]);
+                        output[position] = acc;
+                    }
+                }
+            }
+        }
+        Ok(Arc::new(output))
+    }
+}
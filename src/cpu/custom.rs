@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use crate::core::custom::CustomOp;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{data_size, Tensor};
+
+impl<T: Element> CpuOperator<T> for CustomOp {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let mut input = Vec::with_capacity(tensor.arguments().len());
+        for arg in tensor.arguments() {
+            input.push(context.compute(arg)?);
+        }
+        // 闭包只接受 f32, 其他类型需要转换
+        let input = input
+            .iter()
+            .map(|x| x.iter().map(|x| x.to_f32()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let input = input.iter().map(|x| x.as_slice()).collect::<Vec<_>>();
+        let output = self.forward(&input, data_size(tensor.shape()));
+        Ok(Arc::new(T::from_f32_slice(&output)))
+    }
+}
@@ -0,0 +1,11 @@
+use crate::core::detach::Detach;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::Tensor;
+
+impl<T: Element> CpuOperator<T> for Detach {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [arg] = tensor.arguments() else { panic!() };
+        context.compute(arg)
+    }
+}
@@ -1,11 +1,12 @@
 use std::sync::Arc;
 
 use crate::core::div_tensor::DivTensor;
-use crate::cpu::{CpuContext, CpuOperator};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
 use crate::tensor::{data_size, Tensor};
 
-impl CpuOperator for DivTensor {
-    fn compute(&self, tensor: &Tensor, context: &mut CpuContext) -> Result<Arc<Vec<f32>>, ()> {
+impl<T: Element> CpuOperator<T> for DivTensor {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
         let [a, b] = tensor.arguments() else { panic!() };
         let a_input = context.compute(a)?;
         let b_input = context.compute(b)?;
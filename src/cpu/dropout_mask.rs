@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use crate::core::dropout_mask::DropoutMask;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{data_size, Tensor};
+
+impl<T: Element> CpuOperator<T> for DropoutMask {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let len = data_size(tensor.shape());
+        if !context.training() {
+            return Ok(Arc::new(vec![T::ONE; len]));
+        }
+        let p = self.p() as f64;
+        let scale = T::from_f64(1.0 / (1.0 - p));
+        let rng = context.rng();
+        let output = (0..len)
+            .map(|_| if rng.gen::<f64>() < p { T::ZERO } else { scale })
+            .collect();
+        Ok(Arc::new(output))
+    }
+}
+
+#[test]
+fn test() {
+    let x = Tensor::variable([1000]);
+    let y = x.dropout(0.5);
+    let g = y.back(&x);
+    let mut context = CpuContext::new();
+    context.set_seed(7);
+    context.input(&x, Arc::new(vec![2.0; 1000]));
+    let value = context.compute(&y).unwrap();
+    let grad = context.compute(&g).unwrap();
+    let kept = value.iter().filter(|&&x| x != 0.0).count();
+    assert!((400..600).contains(&kept));
+    // 反向使用同一个掩码
+    for (v, g) in value.iter().zip(grad.iter()) {
+        assert!(*v == 0.0 && *g == 0.0 || *v == 4.0 && *g == 2.0);
+    }
+
+    // 相同的种子得到相同的掩码
+    let mut other = CpuContext::new();
+    other.set_seed(7);
+    other.input(&x, Arc::new(vec![2.0; 1000]));
+    assert_eq!(other.compute(&y).unwrap(), value);
+
+    let mut eval = CpuContext::new();
+    eval.set_training(false);
+    eval.input(&x, Arc::new(vec![2.0; 1000]));
+    assert_eq!(eval.compute(&y).unwrap().as_slice(), [2.0; 1000]);
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use crate::core::embedding_lookup::EmbeddingLookup;
+use crate::cpu::{checked_index, CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::Tensor;
+
+impl<T: Element> CpuOperator<T> for EmbeddingLookup {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [table, indices] = tensor.arguments() else { panic!() };
+        let &[vocab, dim] = table.shape() else { panic!() };
+        let table = context.compute(table)?;
+        let indices = context.compute(indices)?;
+
+        let mut output = Vec::with_capacity(indices.len() * dim);
+        for position in 0..indices.len() {
+            let i = checked_index(&indices, position, vocab)?;
+            output.extend_from_slice(&table[i * dim..(i + 1) * dim]);
+        }
+        Ok(Arc::new(output))
+    }
+}
+
+#[test]
+fn test() {
+    use crate::cpu::CpuError;
+
+    let table = Tensor::constant([2, 1], Arc::new(vec![1.0, 2.0]));
+    let indices = Tensor::indices([2], Arc::new(vec![1, 2]));
+    assert_eq!(
+        table.embedding(indices).compute(),
+        Err(CpuError::IndexOutOfBounds {
+            position: 1,
+            index: 2,
+            size: 2
+        })
+    );
+}
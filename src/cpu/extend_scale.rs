@@ -1,11 +1,12 @@
 use std::sync::Arc;
 
 use crate::core::extend_scale::ExtendScale;
-use crate::cpu::{CpuContext, CpuOperator};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
 use crate::tensor::{data_size, Tensor};
 
-impl CpuOperator for ExtendScale {
-    fn compute(&self, tensor: &Tensor, context: &mut CpuContext) -> Result<Arc<Vec<f32>>, ()> {
+impl<T: Element> CpuOperator<T> for ExtendScale {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
         let [scale] = tensor.arguments() else { panic!() };
         let &[value] = context.compute(scale)?.as_slice() else { panic!() };
         Ok(Arc::new(vec![value; data_size(tensor.shape())]))
@@ -1,11 +1,12 @@
 use std::sync::Arc;
 
 use crate::core::function::Function;
-use crate::cpu::{CpuContext, CpuOperator};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
 use crate::tensor::{data_size, Tensor};
 
-impl CpuOperator for Function {
-    fn compute(&self, tensor: &Tensor, context: &mut CpuContext) -> Result<Arc<Vec<f32>>, ()> {
+impl<T: Element> CpuOperator<T> for Function {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
         let [arg] = tensor.arguments() else { panic!() };
         let input_data = context.compute(arg)?;
         let input = input_data.as_slice();
@@ -15,36 +16,36 @@ impl CpuOperator for Function {
         match *self {
             Function::Sin => {
                 for i in 0..len {
-                    data.push(f32::sin(input[i]));
+                    data.push(T::sin(input[i]));
                 }
             }
             Function::Cos => {
                 for i in 0..len {
-                    data.push(f32::cos(input[i]));
+                    data.push(T::cos(input[i]));
                 }
             }
             Function::ReLU => {
                 for i in 0..len {
-                    data.push(f32::max(input[i], 0.0));
+                    data.push(T::max(input[i], T::ZERO));
                 }
             }
             Function::Step => {
                 for i in 0..len {
                     data.push(if input[i].is_sign_positive() {
-                        1.0
+                        T::ONE
                     } else {
-                        0.0
+                        T::ZERO
                     });
                 }
             }
             Function::Abs => {
                 for i in 0..len {
-                    data.push(f32::abs(input[i]));
+                    data.push(T::abs(input[i]));
                 }
             }
             Function::Sig => {
                 for i in 0..len {
-                    data.push(f32::signum(input[i]));
+                    data.push(T::signum(input[i]));
                 }
             }
             Function::Neg => {
@@ -55,7 +56,7 @@ impl CpuOperator for Function {
             Function::Mul(x) => {
                 if x == -2.0 {
                     for i in 0..len {
-                        data.push(input[i] * -2.0);
+                        data.push(-(input[i] + input[i]));
                     }
                 } else if x == -1.0 {
                     for i in 0..len {
@@ -63,15 +64,16 @@ impl CpuOperator for Function {
                     }
                 } else if x == 0.0 {
                     for _ in 0..len {
-                        data.push(0.0);
+                        data.push(T::ZERO);
                     }
                 } else if x == 1.0 {
                     return Ok(input_data.clone());
                 } else if x == 2.0 {
                     for i in 0..len {
-                        data.push(input[i] * 2.0);
+                        data.push(input[i] + input[i]);
                     }
                 } else {
+                    let x = T::from_f64(x);
                     for i in 0..len {
                         data.push(input[i] * x);
                     }
@@ -81,6 +83,7 @@ impl CpuOperator for Function {
                 if x == 0.0 {
                     return Ok(input_data.clone());
                 } else {
+                    let x = T::from_f64(x);
                     for i in 0..len {
                         data.push(input[i] + x);
                     }
@@ -89,11 +92,11 @@ impl CpuOperator for Function {
             Function::Pow(x) => {
                 if x == -1.0 {
                     for i in 0..len {
-                        data.push(1.0 / input[i]);
+                        data.push(T::ONE / input[i]);
                     }
                 } else if x == 0.0 {
                     for _ in 0..len {
-                        data.push(0.0);
+                        data.push(T::ONE);
                     }
                 } else if x == 1.0 {
                     return Ok(input_data.clone());
@@ -102,6 +105,7 @@ impl CpuOperator for Function {
                         data.push(input[i] * input[i]);
                     }
                 } else {
+                    let x = T::from_f64(x);
                     for i in 0..len {
                         data.push(input[i].powf(x));
                     }
@@ -109,7 +113,23 @@ impl CpuOperator for Function {
             }
             Function::Sigmoid => {
                 for i in 0..len {
-                    data.push(1.0 / ((-input[i]).exp() + 1.0));
+                    data.push(T::ONE / ((-input[i]).exp() + T::ONE));
+                }
+            }
+            Function::Exp => {
+                for &x in input {
+                    data.push(T::exp(x));
+                }
+            }
+            Function::Ln => {
+                for &x in input {
+                    data.push(T::ln(x));
+                }
+            }
+            Function::Clamp(min, max) => {
+                let (min, max) = (T::from_f64(min), T::from_f64(max));
+                for &x in input {
+                    data.push(x.max(min).min(max));
                 }
             }
         }
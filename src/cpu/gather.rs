@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use crate::core::gather::Gather;
+use crate::cpu::{checked_index, CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{axis_split, Tensor};
+
+impl<T: Element> CpuOperator<T> for Gather {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [input, indices] = tensor.arguments() else { panic!() };
+        let (_, size, inner) = axis_split(input.shape(), self.axis());
+        let (outer, len, _) = axis_split(indices.shape(), self.axis());
+        let input = context.compute(input)?;
+        let indices = context.compute(indices)?;
+
+        let mut output = Vec::with_capacity(indices.len());
+        for o in 0..outer {
+            for i in 0..len {
+                for n in 0..inner {
+                    let position = (o * len + i) * inner + n;
+                    let index = checked_index(&indices, position, size)?;
+                    output.push(input[(o * size + index) * inner + n]);
+                }
+            }
+        }
+        Ok(Arc::new(output))
+    }
+}
@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+use crate::core::group_mean::{groups, GroupMean};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::Tensor;
+
+impl<T: Element> CpuOperator<T> for GroupMean {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [arg] = tensor.arguments() else { panic!() };
+        let input = context.compute(arg)?;
+        let (group, count, size) = groups(arg.shape(), self.axes());
+        let mut sum = vec![T::ZERO; count];
+        for (&g, &x) in group.iter().zip(input.iter()) {
+            sum[g] += x;
+        }
+        let size = T::from_f64(size as f64);
+        Ok(Arc::new(group.into_iter().map(|g| sum[g] / size).collect()))
+    }
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use crate::core::index_add::IndexAdd;
+use crate::cpu::{checked_index, CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{axis_split, data_size, Tensor};
+
+impl<T: Element> CpuOperator<T> for IndexAdd {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [indices, source] = tensor.arguments() else { panic!() };
+        let (outer, size, inner) = axis_split(tensor.shape(), self.axis());
+        let len = source.shape()[self.axis()];
+        let indices = context.compute(indices)?;
+        let source = context.compute(source)?;
+
+        let mut output = vec![T::ZERO; data_size(tensor.shape())];
+        for position in 0..len {
+            let index = checked_index(&indices, position, size)?;
+            for o in 0..outer {
+                let from = (o * len + position) * inner;
+                let to = (o * size + index) * inner;
+                for n in 0..inner {
+                    output[to + n] += source[from + n];
+                }
+            }
+        }
+        Ok(Arc::new(output))
+    }
+}
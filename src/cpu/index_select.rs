@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use crate::core::index_select::IndexSelect;
+use crate::cpu::{checked_index, CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{axis_split, data_size, Tensor};
+
+impl<T: Element> CpuOperator<T> for IndexSelect {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [input, indices] = tensor.arguments() else { panic!() };
+        let (outer, size, inner) = axis_split(input.shape(), self.axis());
+        let input = context.compute(input)?;
+        let indices = context.compute(indices)?;
+
+        let mut index = Vec::with_capacity(indices.len());
+        for position in 0..indices.len() {
+            index.push(checked_index(&indices, position, size)?);
+        }
+
+        let mut output = Vec::with_capacity(data_size(tensor.shape()));
+        for o in 0..outer {
+            for &i in &index {
+                let from = (o * size + i) * inner;
+                output.extend_from_slice(&input[from..from + inner]);
+            }
+        }
+        Ok(Arc::new(output))
+    }
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::core::linear_scan::LinearScan;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{axis_split, Tensor};
+
+impl<T: Element> CpuOperator<T> for LinearScan {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [a, b] = tensor.arguments() else { panic!() };
+        let (outer, size, inner) = axis_split(b.shape(), self.axis);
+        let (a, b) = (context.compute(a)?, context.compute(b)?);
+
+        let mut output = vec![T::ZERO; b.len()];
+        for o in 0..outer {
+            for n in 0..inner {
+                let mut acc = T::ZERO;
+                let mut previous = None;
+                for i in 0..size {
+                    let i = if self.reverse { size - 1 - i } else { i };
+                    let position = (o * size + i) * inner + n;
+                    // 第一个位置没有前项, 不乘系数, 以免 inf * 0 得到 NaN
+                    acc = match previous {
+                        Some(previous) => {
+                            let c = if self.shifted { previous } else { position };
+                            b[position] + a[c] * acc
+                        }
+                        None => b[position],
+                    };
+                    output[position] = acc;
+                    previous = Some(position);
+                }
+            }
+        }
+        Ok(Arc::new(output))
+    }
+}
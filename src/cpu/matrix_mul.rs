@@ -1,11 +1,12 @@
 use std::sync::Arc;
 
 use crate::core::matrix_mul::MatrixMul;
-use crate::cpu::{CpuContext, CpuOperator};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
 use crate::tensor::Tensor;
 
-impl CpuOperator for MatrixMul {
-    fn compute(&self, tensor: &Tensor, context: &mut CpuContext) -> Result<Arc<Vec<f32>>, ()> {
+impl<T: Element> CpuOperator<T> for MatrixMul {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
         let [a, b] = tensor.arguments() else { panic!() };
         let a_input = context.compute(a)?;
         let b_input = context.compute(b)?;
@@ -31,7 +32,7 @@ impl CpuOperator for MatrixMul {
 
                 for i in 0..o1 {
                     for j in 0..o2 {
-                        let mut sum = 0.0;
+                        let mut sum = T::ZERO;
                         for k in 0..len {
                             sum += a_input[i * a2 + k] * b_input[k * b2 + j];
                         }
@@ -47,7 +48,7 @@ impl CpuOperator for MatrixMul {
 
                 for i in 0..o1 {
                     for j in 0..o2 {
-                        let mut sum = 0.0;
+                        let mut sum = T::ZERO;
                         for k in 0..len {
                             sum += a_input[i * a2 + k] * b_input[k + j * b2];
                         }
@@ -63,7 +64,7 @@ impl CpuOperator for MatrixMul {
 
                 for i in 0..o1 {
                     for j in 0..o2 {
-                        let mut sum = 0.0;
+                        let mut sum = T::ZERO;
                         for k in 0..len {
                             sum += a_input[i + k * a2] * b_input[k * b2 + j];
                         }
@@ -79,7 +80,7 @@ impl CpuOperator for MatrixMul {
 
                 for i in 0..o1 {
                     for j in 0..o2 {
-                        let mut sum = 0.0;
+                        let mut sum = T::ZERO;
                         for k in 0..len {
                             sum += a_input[i + k * a2] * b_input[k + j * b2];
                         }
@@ -2,21 +2,22 @@ use std::ops::Deref;
 use std::sync::Arc;
 
 use crate::core::merge_tensor::MergeTensor;
-use crate::cpu::{CpuContext, CpuOperator};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
 use crate::tensor::{data_size, Tensor};
 
-impl CpuOperator for MergeTensor {
-    fn compute(&self, tensor: &Tensor, context: &mut CpuContext) -> Result<Arc<Vec<f32>>, ()> {
+impl<T: Element> CpuOperator<T> for MergeTensor {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
         let all_size = data_size(tensor.shape());
 
         let mut output = Vec::with_capacity(all_size);
 
-        output.append(&mut vec![0.0; self.fill()]);
+        output.append(&mut vec![T::ZERO; self.fill()]);
         for i in tensor.arguments() {
             let mut value = context.compute(i)?.deref().clone();
             output.append(&mut value);
         }
-        output.append(&mut vec![0.0; all_size - output.len()]);
+        output.append(&mut vec![T::ZERO; all_size - output.len()]);
 
         Ok(Arc::new(output))
     }
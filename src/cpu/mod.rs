@@ -1,105 +1,407 @@
-use std::any::TypeId;
+use std::any::{Any, TypeId};
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
-use std::sync::{Arc, OnceLock};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::{Arc, OnceLock, RwLock};
 
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use crate::backend::Backend;
 use crate::core::add_tensor::AddTensor;
+use crate::core::arg_sort::ArgSort;
 use crate::core::assign::Assign;
+use crate::core::binary::Binary;
+use crate::core::cast::Cast;
+use crate::core::categorical::Categorical;
+use crate::core::compare::Compare;
+use crate::core::concat::Concat;
 use crate::core::constant::Constant;
+use crate::core::cumulative::Cumulative;
+use crate::core::custom::CustomOp;
 use crate::core::debug_assign::DebugAssign;
+use crate::core::detach::Detach;
 use crate::core::div_tensor::DivTensor;
+use crate::core::dropout_mask::DropoutMask;
+use crate::core::embedding_lookup::EmbeddingLookup;
 use crate::core::extend_scale::ExtendScale;
+use crate::core::flip::Flip;
 use crate::core::function::Function;
+use crate::core::gather::Gather;
+use crate::core::group_mean::GroupMean;
+use crate::core::index_add::IndexAdd;
+use crate::core::index_select::IndexSelect;
+use crate::core::linear_scan::LinearScan;
 use crate::core::matrix_mul::MatrixMul;
 use crate::core::merge_tensor::MergeTensor;
 use crate::core::mul_tensor::MulTensor;
+use crate::core::normalize::Normalize;
+use crate::core::pad::Pad;
+use crate::core::pad_grad::PadGrad;
+use crate::core::random::Random;
 use crate::core::reshape::Reshape;
+use crate::core::scatter_add::ScatterAdd;
 use crate::core::select::Select;
 use crate::core::slice_tensor::SliceTensor;
+use crate::core::straight_through::StraightThrough;
+use crate::core::strided_scatter::StridedScatter;
+use crate::core::strided_slice::StridedSlice;
 use crate::core::sub_tensor::SubTensor;
 use crate::core::sum_scale::SumScale;
+use crate::core::tile::Tile;
+use crate::core::tile_sum::TileSum;
+use crate::core::training::Training;
 use crate::core::variable::Variable;
+use crate::core::where_tensor::WhereTensor;
 use crate::core::TensorOperator;
+use crate::dtype::DType;
+use crate::element::Element;
 use crate::tensor::{data_size, Tensor, TensorHandle};
 
 pub mod add_tensor;
+pub mod arg_sort;
 pub mod assign;
+pub mod binary;
+pub mod cast;
+pub mod categorical;
+pub mod compare;
+pub mod concat;
 pub mod constant;
+pub mod cumulative;
+pub mod custom;
 pub mod debug_assign;
+pub mod detach;
 pub mod div_tensor;
+pub mod dropout_mask;
+pub mod embedding_lookup;
 pub mod extend_scale;
+pub mod flip;
 pub mod function;
+pub mod gather;
+pub mod group_mean;
+pub mod index_add;
+pub mod index_select;
+pub mod linear_scan;
 pub mod matrix_mul;
 pub mod merge_tensor;
 pub mod mul_tensor;
+pub mod normalize;
+pub mod pad;
+pub mod pad_grad;
+pub mod random;
 pub mod reshape;
+pub mod scatter_add;
 pub mod select;
 pub mod slice_tensor;
+pub mod straight_through;
+pub mod strided_scatter;
+pub mod strided_slice;
 pub mod sub_tensor;
 pub mod sum_scale;
+pub mod tile;
+pub mod tile_sum;
+pub mod training;
 pub mod variable;
+pub mod where_tensor;
 
-pub trait CpuOperator: TensorOperator {
-    fn compute(&self, tensor: &Tensor, context: &mut CpuContext) -> Result<Arc<Vec<f32>>, ()>;
+pub trait CpuOperator<T: Element = f32>: TensorOperator {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T>;
 }
 
-pub fn operator_as_cpu(r: &dyn TensorOperator) -> Option<&dyn CpuOperator> {
-    static TYPE_MAP: OnceLock<HashMap<TypeId, fn(&dyn TensorOperator) -> &dyn CpuOperator>> =
-        OnceLock::new();
+pub type CpuResult<T = f32> = Result<Arc<Vec<T>>, CpuError>;
 
-    fn init_map() -> HashMap<TypeId, fn(&dyn TensorOperator) -> &dyn CpuOperator> {
-        let mut map = HashMap::new();
-        let m = &mut map;
+#[derive(Debug, Clone, PartialEq)]
+pub enum CpuError {
+    /// 算子没有注册 CPU 实现, 内容为算子的名称
+    NoKernel(String),
+    /// 变量没有输入值, 内容为变量编号
+    NoInput(u32),
+    /// 下标张量的第 `position` 个值 `index` 不在 `0..size` 内
+    IndexOutOfBounds {
+        position: usize,
+        index: i64,
+        size: usize,
+    },
+    /// 下标张量的第 `position` 个值 `value` 不是有限的整数
+    InvalidIndex { position: usize, value: f64 },
+    /// 类别分布的第 `row` 行权重有负数或 NaN, 或总和不是正的有限数
+    InvalidWeights { row: usize },
+}
 
-        fn insert<T: CpuOperator>(
-            map: &mut HashMap<TypeId, fn(&dyn TensorOperator) -> &dyn CpuOperator>,
-        ) {
-            fn cast<T: CpuOperator>(r: &dyn TensorOperator) -> &dyn CpuOperator {
-                r.cast_to::<T>().unwrap()
+impl Display for CpuError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CpuError::NoKernel(name) => write!(f, "the operator {} no support cpu", name),
+            CpuError::NoInput(id) => write!(f, "the variable ${} has no input", id),
+            CpuError::IndexOutOfBounds {
+                position,
+                index,
+                size,
+            } => write!(
+                f,
+                "the index {} at position {} is out of bounds for size {}",
+                index, position, size
+            ),
+            CpuError::InvalidIndex { position, value } => {
+                write!(
+                    f,
+                    "the index {} at position {} is not an integer",
+                    value, position
+                )
             }
+            CpuError::InvalidWeights { row } => {
+                write!(f, "the categorical weights at row {} are invalid", row)
+            }
+        }
+    }
+}
+
+impl Error for CpuError {}
+
+/// 取出 `indices` 的第 `position` 个值作为下标, 须为 `0..size` 内的整数
+pub fn checked_index<T: Element>(
+    indices: &[T],
+    position: usize,
+    size: usize,
+) -> Result<usize, CpuError> {
+    let value = indices[position];
+    let Some(index) = value.to_index() else {
+        return Err(CpuError::InvalidIndex {
+            position,
+            value: value.to_f64(),
+        });
+    };
+    if index < 0 || index as usize >= size {
+        return Err(CpuError::IndexOutOfBounds {
+            position,
+            index,
+            size,
+        });
+    }
+    Ok(index as usize)
+}
 
-            map.insert(TypeId::of::<T>(), cast::<T> as _);
+/// 把整数和布尔张量的值规整为该类型能表示的值: 整数向零取整 (I32 会回绕),
+/// 布尔非零为 1. 浮点类型不变, 已经规整时不复制.
+pub fn conform<T: Element>(dtype: DType, data: Arc<Vec<T>>) -> Arc<Vec<T>> {
+    let f = |x: T| match dtype {
+        DType::Bool => {
+            if x != T::ZERO {
+                T::ONE
+            } else {
+                T::ZERO
+            }
         }
+        DType::I32 => T::from_f64(x.to_f64() as i64 as i32 as f64),
+        DType::I64 => T::from_f64(x.to_f64() as i64 as f64),
+        DType::F32 | DType::F64 => x,
+    };
+    if dtype.is_float() || data.iter().all(|&x| f(x) == x) {
+        return data;
+    }
+    Arc::new(data.iter().map(|&x| f(x)).collect())
+}
 
-        insert::<AddTensor>(m);
-        insert::<Assign>(m);
-        insert::<Constant>(m);
-        insert::<DebugAssign>(m);
-        insert::<DivTensor>(m);
-        insert::<ExtendScale>(m);
-        insert::<Function>(m);
-        insert::<MatrixMul>(m);
-        insert::<MergeTensor>(m);
-        insert::<MulTensor>(m);
-        insert::<Reshape>(m);
-        insert::<Select>(m);
-        insert::<SliceTensor>(m);
-        insert::<SubTensor>(m);
-        insert::<SumScale>(m);
-        insert::<Variable>(m);
+type KernelFn<T> = dyn Fn(&Tensor, &mut CpuContext<T>) -> CpuResult<T> + Send + Sync;
+
+/// 计算某类算子的 CPU 实现
+pub struct CpuKernel<T: Element = f32>(Arc<KernelFn<T>>);
+
+impl<T: Element> CpuKernel<T> {
+    pub fn new<F: Fn(&Tensor, &mut CpuContext<T>) -> CpuResult<T> + Send + Sync + 'static>(
+        f: F,
+    ) -> Self {
+        Self(Arc::new(f))
+    }
 
-        map
+    /// 调用 `O` 自身的 `CpuOperator` 实现
+    pub fn of<O: CpuOperator<T>>() -> Self {
+        Self::new(|tensor, context| {
+            tensor
+                .operator()
+                .cast_to::<O>()
+                .unwrap()
+                .compute(tensor, context)
+        })
     }
 
-    TYPE_MAP
-        .get_or_init(init_map)
-        .get(&r.type_id())
-        .map(|f| f(r))
+    pub fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        (self.0)(tensor, context)
+    }
 }
 
+impl<T: Element> Clone for CpuKernel<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Element> Debug for CpuKernel<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CpuKernel")
+    }
+}
+
+/// 以 (算子类型, 元素类型) 为键, 值为对应的 `CpuKernel<T>`
+type Registry = HashMap<(TypeId, TypeId), Box<dyn Any + Send + Sync>>;
+
+fn registry() -> &'static RwLock<Registry> {
+    static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+
+    fn init_map() -> RwLock<Registry> {
+        let mut map = HashMap::new();
+        insert_all::<f32>(&mut map);
+        insert_all::<f64>(&mut map);
+        RwLock::new(map)
+    }
+
+    fn insert_all<T: Element>(m: &mut Registry) {
+        fn insert<O: CpuOperator<T>, T: Element>(map: &mut Registry) {
+            map.insert(
+                (TypeId::of::<O>(), TypeId::of::<T>()),
+                Box::new(CpuKernel::<T>::of::<O>()),
+            );
+        }
+
+        insert::<AddTensor, T>(m);
+        insert::<ArgSort, T>(m);
+        insert::<Assign, T>(m);
+        insert::<Binary, T>(m);
+        insert::<Cast, T>(m);
+        insert::<Categorical, T>(m);
+        insert::<Compare, T>(m);
+        insert::<Concat, T>(m);
+        insert::<Constant, T>(m);
+        insert::<Cumulative, T>(m);
+        insert::<CustomOp, T>(m);
+        insert::<DebugAssign, T>(m);
+        insert::<Detach, T>(m);
+        insert::<DivTensor, T>(m);
+        insert::<DropoutMask, T>(m);
+        insert::<EmbeddingLookup, T>(m);
+        insert::<ExtendScale, T>(m);
+        insert::<Flip, T>(m);
+        insert::<Function, T>(m);
+        insert::<Gather, T>(m);
+        insert::<GroupMean, T>(m);
+        insert::<IndexAdd, T>(m);
+        insert::<IndexSelect, T>(m);
+        insert::<LinearScan, T>(m);
+        insert::<MatrixMul, T>(m);
+        insert::<MergeTensor, T>(m);
+        insert::<MulTensor, T>(m);
+        insert::<Normalize, T>(m);
+        insert::<Pad, T>(m);
+        insert::<PadGrad, T>(m);
+        insert::<Random, T>(m);
+        insert::<Reshape, T>(m);
+        insert::<ScatterAdd, T>(m);
+        insert::<Select, T>(m);
+        insert::<SliceTensor, T>(m);
+        insert::<StraightThrough, T>(m);
+        insert::<StridedScatter, T>(m);
+        insert::<StridedSlice, T>(m);
+        insert::<SubTensor, T>(m);
+        insert::<SumScale, T>(m);
+        insert::<Tile, T>(m);
+        insert::<TileSum, T>(m);
+        insert::<Training, T>(m);
+        insert::<Variable, T>(m);
+        insert::<WhereTensor, T>(m);
+    }
+
+    REGISTRY.get_or_init(init_map)
+}
+
+/// 为 `O` 注册元素类型为 `T` 的全局 CPU 实现, 会覆盖已有的实现
+pub fn register_cpu_operator<O: CpuOperator<T>, T: Element>() {
+    register_cpu_kernel::<O, T>(CpuKernel::of::<O>());
+}
+
+/// 以任意的 `kernel` 作为 `O` 的全局 CPU 实现, 会覆盖已有的实现
+pub fn register_cpu_kernel<O: TensorOperator, T: Element>(kernel: CpuKernel<T>) {
+    registry()
+        .write()
+        .unwrap()
+        .insert((TypeId::of::<O>(), TypeId::of::<T>()), Box::new(kernel));
+}
+
+pub fn cpu_kernel<T: Element>(r: &dyn TensorOperator) -> Option<CpuKernel<T>> {
+    registry()
+        .read()
+        .unwrap()
+        .get(&(r.type_id(), TypeId::of::<T>()))
+        .and_then(|x| x.downcast_ref::<CpuKernel<T>>())
+        .cloned()
+}
+
+/// 以 `T` 为元素类型的计算上下文, `CpuContext::new` 为 f32,
+/// 其他类型用 `CpuContext::<f64>::default()` 创建
 #[derive(Debug, Clone)]
-pub struct CpuContext {
-    catch: HashMap<TensorHandle, Result<Arc<Vec<f32>>, ()>>,
+pub struct CpuContext<T: Element = f32> {
+    catch: HashMap<TensorHandle, CpuResult<T>>,
+    /// 只在此上下文中生效, 优先于全局注册的实现
+    kernels: HashMap<TypeId, CpuKernel<T>>,
+    /// 随机算子所用, 默认以系统熵初始化
+    rng: SmallRng,
+    /// 为 false 时 dropout 等只在训练时生效的算子为恒等变换
+    training: bool,
+}
+
+impl<T: Element> Default for CpuContext<T> {
+    fn default() -> Self {
+        Self {
+            catch: HashMap::new(),
+            kernels: HashMap::new(),
+            rng: SmallRng::from_entropy(),
+            training: true,
+        }
+    }
 }
 
 impl CpuContext {
     pub fn new() -> Self {
-        Self {
-            catch: Default::default(),
+        Self::default()
+    }
+}
+
+impl<T: Element> CpuContext<T> {
+    /// 以固定的种子重置随机数生成器, 使随机算子的结果可以复现
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    pub fn rng(&mut self) -> &mut SmallRng {
+        &mut self.rng
+    }
+
+    pub fn set_training(&mut self, training: bool) {
+        self.training = training;
+    }
+
+    pub fn training(&self) -> bool {
+        self.training
+    }
+
+    pub fn set_kernel<O: TensorOperator>(&mut self, kernel: CpuKernel<T>) {
+        self.kernels.insert(TypeId::of::<O>(), kernel);
+    }
+
+    /// 与 `set_kernel` 相同, 以 `operator` 的类型为键
+    pub fn set_kernel_of(&mut self, operator: &dyn TensorOperator, kernel: CpuKernel<T>) {
+        self.kernels.insert(operator.type_id(), kernel);
+    }
+
+    /// `operator` 在此上下文中使用的实现
+    pub fn kernel(&self, operator: &dyn TensorOperator) -> Option<CpuKernel<T>> {
+        match self.kernels.get(&operator.type_id()) {
+            Some(kernel) => Some(kernel.clone()),
+            None => cpu_kernel::<T>(operator),
         }
     }
 
-    pub fn input(&mut self, tensor: &Tensor, data: Arc<Vec<f32>>) {
+    pub fn input(&mut self, tensor: &Tensor, data: Arc<Vec<T>>) {
         assert!(tensor.is_variable());
         assert_eq!(data.len(), data_size(tensor.shape()));
         match self.catch.entry(tensor.clone().into()) {
@@ -112,35 +414,132 @@ impl CpuContext {
         }
     }
 
-    pub fn input_constant_with<'s, I: IntoIterator<Item = (&'s Tensor, Arc<Vec<f32>>)>>(
+    /// 以 `i` 为变量的值计算 `value` 并作为 `tensor` 的输入
+    ///
+    /// 在元素类型, 核和训练模式都相同的新上下文中计算, 不影响本上下文的缓存.
+    pub fn input_constant_with<'s, I: IntoIterator<Item = (&'s Tensor, Arc<Vec<T>>)>>(
         &mut self,
         tensor: &Tensor,
         value: &Tensor,
         i: I,
     ) {
-        self.input(tensor, value.compute_with(i).unwrap());
+        let mut context = CpuContext {
+            kernels: self.kernels.clone(),
+            training: self.training,
+            ..CpuContext::default()
+        };
+        for (var, val) in i {
+            context.input(var, val);
+        }
+        let data = context.compute(value).unwrap();
+        self.input(tensor, data);
     }
 
-    pub fn get(&self, tensor: &Tensor) -> Option<Result<Arc<Vec<f32>>, ()>> {
+    pub fn get(&self, tensor: &Tensor) -> Option<CpuResult<T>> {
         self.catch.get(tensor.into()).cloned()
     }
 
-    pub fn compute(&mut self, tensor: &Tensor) -> Result<Arc<Vec<f32>>, ()> {
+    pub fn compute(&mut self, tensor: &Tensor) -> CpuResult<T> {
         let tensor: &TensorHandle = tensor.into();
         match self.catch.get(tensor) {
             Some(x) => x.clone(),
             None => {
-                let result = operator_as_cpu(tensor.operator())
-                    .expect("the operator no support cpu")
-                    .compute(tensor, self);
+                let operator = tensor.operator();
+                let result = match self.kernel(operator) {
+                    Some(kernel) => kernel
+                        .compute(tensor, self)
+                        .map(|x| conform(tensor.dtype(), x)),
+                    None => Err(CpuError::NoKernel(format!("{:?}", operator))),
+                };
                 self.catch.insert(tensor.clone(), result.clone());
                 result
             }
         }
     }
 
-    pub fn compute_as_constant(&mut self, tensor: &Tensor) -> Result<Tensor, ()> {
+    pub fn compute_as_constant(&mut self, tensor: &Tensor) -> Result<Tensor, CpuError> {
         let data = self.compute(tensor)?;
-        Ok(Tensor::constant(tensor.shape(), data))
+        Ok(Constant::from_data(
+            tensor.shape().to_vec(),
+            T::into_data(data),
+        ))
+    }
+}
+
+impl<T: Element> Backend for CpuContext<T> {
+    type Element = T;
+    type Error = CpuError;
+
+    fn input(&mut self, tensor: &Tensor, data: Arc<Vec<T>>) {
+        CpuContext::input(self, tensor, data);
     }
+
+    fn compute(&mut self, tensor: &Tensor) -> Result<(), CpuError> {
+        CpuContext::compute(self, tensor).map(|_| ())
+    }
+
+    fn fetch(&mut self, tensor: &Tensor) -> CpuResult<T> {
+        CpuContext::compute(self, tensor)
+    }
+}
+
+#[test]
+fn test() {
+    #[derive(Debug, Copy, Clone)]
+    struct Twice;
+
+    impl TensorOperator for Twice {
+        fn clone_box(&self) -> Box<dyn TensorOperator> {
+            Box::new(*self)
+        }
+    }
+
+    impl CpuOperator for Twice {
+        fn compute(&self, tensor: &Tensor, context: &mut CpuContext) -> CpuResult {
+            let [x] = tensor.arguments() else { panic!() };
+            let x = context.compute(x)?;
+            Ok(Arc::new(x.iter().map(|x| x * 2.0).collect()))
+        }
+    }
+
+    let x = Tensor::constant([2], Arc::new(vec![1.0, 2.0]));
+    let y = Tensor::new(vec![2], vec![x], Box::new(Twice));
+    assert_eq!(y.compute(), Err(CpuError::NoKernel("Twice".to_string())));
+
+    register_cpu_operator::<Twice, f32>();
+    assert_eq!(y.compute().unwrap().as_slice(), [2.0, 4.0]);
+
+    // 上下文中的实现优先于全局注册的实现
+    let mut context = CpuContext::new();
+    context.set_kernel::<Twice>(CpuKernel::new(|_, _| Ok(Arc::new(vec![0.0; 2]))));
+    assert_eq!(context.compute(&y).unwrap().as_slice(), [0.0, 0.0]);
+
+    // 没有为 f64 注册
+    let mut context = CpuContext::<f64>::default();
+    assert!(matches!(context.compute(&y), Err(CpuError::NoKernel(_))));
+
+    let v = Tensor::variable([1]);
+    assert!(matches!(v.compute(), Err(CpuError::NoInput(_))));
+
+    let indices = [2.0, -1.0, 1.5, f32::NAN];
+    assert_eq!(checked_index(&indices, 0, 3), Ok(2));
+    assert_eq!(
+        checked_index(&indices, 1, 3),
+        Err(CpuError::IndexOutOfBounds {
+            position: 1,
+            index: -1,
+            size: 3
+        })
+    );
+    assert_eq!(
+        checked_index(&indices, 2, 3),
+        Err(CpuError::InvalidIndex {
+            position: 2,
+            value: 1.5
+        })
+    );
+    assert!(matches!(
+        checked_index(&indices, 3, 3),
+        Err(CpuError::InvalidIndex { position: 3, .. })
+    ));
 }
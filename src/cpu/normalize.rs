@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use crate::core::group_mean::groups;
+use crate::core::normalize::Normalize;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::Tensor;
+
+impl<T: Element> CpuOperator<T> for Normalize {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [arg] = tensor.arguments() else { panic!() };
+        let input = context.compute(arg)?;
+        let (group, count, size) = groups(arg.shape(), self.axes());
+
+        // Welford 算法, 避免 E[x^2] - E[x]^2 的抵消误差
+        let mut mean = vec![T::ZERO; count];
+        let mut m2 = vec![T::ZERO; count];
+        let mut n = vec![T::ZERO; count];
+        for (&g, &x) in group.iter().zip(input.iter()) {
+            if self.center() {
+                n[g] += T::ONE;
+                let delta = x - mean[g];
+                mean[g] += delta / n[g];
+                m2[g] += delta * (x - mean[g]);
+            } else {
+                m2[g] += x * x;
+            }
+        }
+        let size = T::from_f64(size as f64);
+        let eps = T::from_f32(self.eps());
+        let r = m2
+            .into_iter()
+            .map(|m2| (m2 / size + eps).powf(T::from_f32(-0.5)))
+            .collect::<Vec<_>>();
+        let output = group
+            .into_iter()
+            .zip(input.iter())
+            .map(|(g, &x)| (x - mean[g]) * r[g])
+            .collect();
+        Ok(Arc::new(output))
+    }
+}
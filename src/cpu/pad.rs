@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use crate::core::pad::{Pad, PadMode};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::Tensor;
+
+impl<T: Element> CpuOperator<T> for Pad {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [arg] = tensor.arguments() else { panic!() };
+        let input = context.compute(arg)?;
+        let value = match self.mode() {
+            PadMode::Constant(x) => T::from_f32(x),
+            _ => T::ZERO,
+        };
+        let offsets = self.offsets(arg.shape());
+        Ok(Arc::new(
+            offsets
+                .into_iter()
+                .map(|i| i.map_or(value, |i| input[i]))
+                .collect(),
+        ))
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::core::pad_grad::PadGrad;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{data_size, Tensor};
+
+impl<T: Element> CpuOperator<T> for PadGrad {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [arg] = tensor.arguments() else { panic!() };
+        let input = context.compute(arg)?;
+        let mut output = vec![T::ZERO; data_size(tensor.shape())];
+        let offsets = self.pad().offsets(tensor.shape());
+        for (i, &x) in offsets.into_iter().zip(input.iter()) {
+            if let Some(i) = i {
+                output[i] += x;
+            }
+        }
+        Ok(Arc::new(output))
+    }
+}
+
+#[test]
+fn test() {
+    use crate::core::pad::PadMode;
+
+    let x = Tensor::variable([3]);
+    let y = x.pad(&[[2, 1]], PadMode::Reflect);
+    let g = y
+        .back(&x)
+        .compute_with([(&x, Arc::new(vec![0.0; 3]))])
+        .unwrap();
+    assert_eq!(g.as_slice(), [1.0, 3.0, 2.0]);
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::Normal;
+
+use crate::core::random::{Distribution, Random};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{data_size, Tensor};
+
+impl<T: Element> CpuOperator<T> for Random {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let len = data_size(tensor.shape());
+        let mut seeded;
+        let rng = match self.seed() {
+            Some(seed) => {
+                seeded = SmallRng::seed_from_u64(seed);
+                &mut seeded
+            }
+            None => context.rng(),
+        };
+        let output = match self.distribution() {
+            Distribution::Uniform(low, high) => {
+                let (low, high) = (low as f64, high as f64);
+                (0..len)
+                    .map(|_| T::from_f64(rng.gen_range(low..high)))
+                    .collect()
+            }
+            Distribution::Normal(mean, std) => {
+                let normal = Normal::new(mean as f64, std as f64).unwrap();
+                (0..len).map(|_| T::from_f64(rng.sample(normal))).collect()
+            }
+            Distribution::Bernoulli(p) => {
+                let p = p as f64;
+                (0..len)
+                    .map(|_| {
+                        if rng.gen::<f64>() < p {
+                            T::ONE
+                        } else {
+                            T::ZERO
+                        }
+                    })
+                    .collect()
+            }
+        };
+        Ok(Arc::new(output))
+    }
+}
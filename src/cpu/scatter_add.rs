@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use crate::core::scatter_add::ScatterAdd;
+use crate::cpu::{checked_index, CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{axis_split, data_size, Tensor};
+
+impl<T: Element> CpuOperator<T> for ScatterAdd {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [indices, source] = tensor.arguments() else { panic!() };
+        let (_, size, inner) = axis_split(tensor.shape(), self.axis());
+        let (outer, len, _) = axis_split(source.shape(), self.axis());
+        let indices = context.compute(indices)?;
+        let source = context.compute(source)?;
+
+        let mut output = vec![T::ZERO; data_size(tensor.shape())];
+        for o in 0..outer {
+            for i in 0..len {
+                for n in 0..inner {
+                    let position = (o * len + i) * inner + n;
+                    let index = checked_index(&indices, position, size)?;
+                    output[(o * size + index) * inner + n] += source[position];
+                }
+            }
+        }
+        Ok(Arc::new(output))
+    }
+}
@@ -1,15 +1,14 @@
-use std::sync::Arc;
-
 use crate::core::select::Select;
-use crate::cpu::{CpuContext, CpuOperator};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
 use crate::tensor::Tensor;
 
-impl CpuOperator for Select {
-    fn compute(&self, tensor: &Tensor, context: &mut CpuContext) -> Result<Arc<Vec<f32>>, ()> {
+impl<T: Element> CpuOperator<T> for Select {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
         let [cond, pos, neg] = tensor.arguments() else { panic!() };
         let cond_data = context.compute(cond)?;
 
         let &[value] = cond_data.as_slice() else { panic!() };
-        context.compute(if value > 0.0 { pos } else { neg })
+        context.compute(if value > T::ZERO { pos } else { neg })
     }
 }
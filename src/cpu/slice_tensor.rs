@@ -1,11 +1,12 @@
 use std::sync::Arc;
 
 use crate::core::slice_tensor::SliceTensor;
-use crate::cpu::{CpuContext, CpuOperator};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
 use crate::tensor::{data_size, Tensor};
 
-impl CpuOperator for SliceTensor {
-    fn compute(&self, tensor: &Tensor, context: &mut CpuContext) -> Result<Arc<Vec<f32>>, ()> {
+impl<T: Element> CpuOperator<T> for SliceTensor {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
         let [arg] = tensor.arguments() else { panic!() };
         let input = context.compute(arg)?;
 
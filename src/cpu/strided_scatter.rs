@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use crate::core::strided_scatter::StridedScatter;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{data_size, Tensor};
+
+impl<T: Element> CpuOperator<T> for StridedScatter {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [arg] = tensor.arguments() else { panic!() };
+        let input = context.compute(arg)?;
+        let mut output = vec![T::ZERO; data_size(tensor.shape())];
+        let offsets = self.slice().offsets(tensor.shape(), arg.shape());
+        for (&i, &x) in offsets.iter().zip(input.iter()) {
+            output[i] = x;
+        }
+        Ok(Arc::new(output))
+    }
+}
+
+#[test]
+fn test() {
+    use crate::core::strided_slice::SliceSpec;
+
+    let x = Tensor::variable([2, 3]);
+    let y = x.slice(&[SliceSpec::Index(1), SliceSpec::step(2)]);
+    let g = y
+        .back(&x)
+        .compute_with([(&x, Arc::new(vec![0.0; 6]))])
+        .unwrap();
+    assert_eq!(g.as_slice(), [0.0, 0.0, 0.0, 1.0, 0.0, 1.0]);
+}
@@ -1,13 +1,14 @@
 use std::sync::Arc;
 
 use crate::core::sum_scale::SumScale;
-use crate::cpu::{CpuContext, CpuOperator};
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
 use crate::tensor::Tensor;
 
-impl CpuOperator for SumScale {
-    fn compute(&self, tensor: &Tensor, context: &mut CpuContext) -> Result<Arc<Vec<f32>>, ()> {
+impl<T: Element> CpuOperator<T> for SumScale {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
         let [scale] = tensor.arguments() else { panic!() };
         let value = context.compute(scale)?;
-        Ok(Arc::new(vec![value.iter().sum()]))
+        Ok(Arc::new(vec![value.iter().copied().sum()]))
     }
 }
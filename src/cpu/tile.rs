@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use crate::core::tile::Tile;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::Tensor;
+
+impl<T: Element> CpuOperator<T> for Tile {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [arg] = tensor.arguments() else { panic!() };
+        let input = context.compute(arg)?;
+        let offsets = Tile::offsets(tensor.shape(), arg.shape());
+        Ok(Arc::new(offsets.into_iter().map(|i| input[i]).collect()))
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use crate::core::tile::Tile;
+use crate::core::tile_sum::TileSum;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{data_size, Tensor};
+
+impl<T: Element> CpuOperator<T> for TileSum {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [arg] = tensor.arguments() else { panic!() };
+        let input = context.compute(arg)?;
+        let mut output = vec![T::ZERO; data_size(tensor.shape())];
+        let offsets = Tile::offsets(arg.shape(), tensor.shape());
+        for (i, &x) in offsets.into_iter().zip(input.iter()) {
+            output[i] += x;
+        }
+        Ok(Arc::new(output))
+    }
+}
+
+#[test]
+fn test() {
+    let x = Tensor::variable([2]);
+    let y = x.tile(&[3]);
+    let g = y
+        .back(&x)
+        .compute_with([(&x, Arc::new(vec![0.0; 2]))])
+        .unwrap();
+    assert_eq!(g.as_slice(), [3.0, 3.0]);
+}
@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use crate::core::training::Training;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::Tensor;
+
+impl<T: Element> CpuOperator<T> for Training {
+    fn compute(&self, _tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        Ok(Arc::new(vec![if context.training() {
+            T::ONE
+        } else {
+            T::ZERO
+        }]))
+    }
+}
+
+#[test]
+fn test() {
+    let x = Training::tensor();
+    let mut context = CpuContext::new();
+    assert_eq!(context.compute(&x).unwrap().as_slice(), [1.0]);
+    let mut context = CpuContext::new();
+    context.set_training(false);
+    assert_eq!(context.compute(&x).unwrap().as_slice(), [0.0]);
+}
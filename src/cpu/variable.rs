@@ -1,12 +1,10 @@
-use std::sync::Arc;
-
 use crate::core::variable::Variable;
-use crate::cpu::{CpuContext, CpuOperator};
+use crate::cpu::{CpuContext, CpuError, CpuOperator, CpuResult};
+use crate::element::Element;
 use crate::tensor::Tensor;
 
-impl CpuOperator for Variable {
-    fn compute(&self, _tensor: &Tensor, _context: &mut CpuContext) -> Result<Arc<Vec<f32>>, ()> {
-        eprintln!("${} = None", self.variable_id());
-        Err(())
+impl<T: Element> CpuOperator<T> for Variable {
+    fn compute(&self, _tensor: &Tensor, _context: &mut CpuContext<T>) -> CpuResult<T> {
+        Err(CpuError::NoInput(self.variable_id()))
     }
 }
@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use crate::core::where_tensor::WhereTensor;
+use crate::cpu::{CpuContext, CpuOperator, CpuResult};
+use crate::element::Element;
+use crate::tensor::{data_size, Tensor};
+
+impl<T: Element> CpuOperator<T> for WhereTensor {
+    fn compute(&self, tensor: &Tensor, context: &mut CpuContext<T>) -> CpuResult<T> {
+        let [mask, pos, neg] = tensor.arguments() else { panic!() };
+        let mask = context.compute(mask)?;
+        let pos = context.compute(pos)?;
+        let neg = context.compute(neg)?;
+
+        let len = data_size(tensor.shape());
+        assert_eq!(mask.len(), len);
+        assert_eq!(pos.len(), len);
+        assert_eq!(neg.len(), len);
+
+        let mut output = Vec::with_capacity(len);
+        for i in 0..len {
+            output.push(if mask[i] > T::ZERO { pos[i] } else { neg[i] });
+        }
+        Ok(Arc::new(output))
+    }
+}
@@ -5,6 +5,7 @@ use crate::core::add_tensor::AddTensor;
 use crate::core::function::Function;
 use crate::core::sum_scale::SumScale;
 use crate::cpu::CpuContext;
+use crate::initializer::Initializer;
 use crate::model_context::ModelContext;
 use crate::tensor::{data_size, Tensor};
 use crate::tools::idx::IdxFile;
@@ -16,22 +17,42 @@ fn mnist_model_build(model: &mut ModelContext, input: &Tensor) -> Tensor {
     let layer = input.reshape([1, INPUT]);
 
     const M1: usize = 150;
-    let fc = model.variable([INPUT, M1]);
-    let layer = layer.matrix_mul(fc).reshape([M1]) + model.variable([M1]);
+    let fc = model
+        .scope("fc1")
+        .named_variable_rng("weight", [INPUT, M1], Initializer::HeNormal);
+    let bias = model
+        .scope("fc1")
+        .named_variable_rng("bias", [M1], Initializer::Zeros);
+    let layer = layer.matrix_mul(fc).reshape([M1]) + bias;
     let layer = (layer.apply(Function::ReLU) + layer * 0.01).reshape([1, M1]);
 
     const M2: usize = 50;
-    let fc = model.variable([M1, M2]);
-    let layer = layer.matrix_mul(fc).reshape([M2]) + model.variable([M2]);
+    let fc = model
+        .scope("fc2")
+        .named_variable_rng("weight", [M1, M2], Initializer::HeNormal);
+    let bias = model
+        .scope("fc2")
+        .named_variable_rng("bias", [M2], Initializer::Zeros);
+    let layer = layer.matrix_mul(fc).reshape([M2]) + bias;
     let layer = (layer.apply(Function::ReLU) + layer * 0.01).reshape([1, M2]);
 
     const M3: usize = 20;
-    let fc = model.variable([M2, M3]);
-    let layer = layer.matrix_mul(fc).reshape([M3]) + model.variable([M3]);
+    let fc = model
+        .scope("fc3")
+        .named_variable_rng("weight", [M2, M3], Initializer::HeNormal);
+    let bias = model
+        .scope("fc3")
+        .named_variable_rng("bias", [M3], Initializer::Zeros);
+    let layer = layer.matrix_mul(fc).reshape([M3]) + bias;
     let layer = (layer.apply(Function::ReLU) + layer * 0.01).reshape([1, M3]);
 
-    let fc = model.variable([M3, 10]);
-    let layer = layer.matrix_mul(fc).reshape([10]) + model.variable([10]);
+    let fc = model
+        .scope("fc4")
+        .named_variable_rng("weight", [M3, 10], Initializer::HeNormal);
+    let bias = model
+        .scope("fc4")
+        .named_variable_rng("bias", [10], Initializer::Zeros);
+    let layer = layer.matrix_mul(fc).reshape([10]) + bias;
     let layer = layer.apply(Function::ReLU) + layer * 0.01;
 
     layer
@@ -140,19 +161,14 @@ pub fn main() {
     let (test_result, test_labels) = read_train_labels("data/mnist/t10k-labels.idx1-ubyte");
     assert_eq!(test_labels.shape(), [10000, 10]);
 
+    let predict = output.argmax(output.shape().len() - 1);
     let mut cross = vec![0; 100];
     let mut correct = 0;
     for i in 0..test_result.len() {
         let mut context = CpuContext::new();
         context.input_constant_with(input, &test_data.get([i]), []);
         model.load_to(&mut context);
-        let out = context.compute(output).unwrap();
-        let o = out
-            .iter()
-            .enumerate()
-            .max_by(|&(_, a), &(_, b)| a.total_cmp(b))
-            .unwrap()
-            .0;
+        let o = context.compute(&predict).unwrap()[0] as usize;
         cross[test_result[i] as usize * 10 + o] += 1;
         if test_result[i] as usize == o {
             correct += 1;
@@ -0,0 +1,71 @@
+use std::fmt::{Display, Formatter};
+
+/// 张量的数据类型
+///
+/// CPU 上的值仍以上下文的元素类型保存: 整数为整数值的浮点数, 布尔为 0 或 1.
+/// 每个算子的结果都按其类型规整 (见 `cpu::conform`): 整数向零取整, 所以整数除法截断,
+/// I32 溢出时回绕; 布尔非零为 1, 所以布尔相加为逻辑或.
+///
+/// 整数只在元素类型的精度内准确, f32 为 2^24, f64 为 2^53, 更大的下标应在
+/// `CpuContext::<f64>` 中计算. 整数与浮点混合运算的结果为浮点.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum DType {
+    Bool,
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+impl DType {
+    pub fn is_float(self) -> bool {
+        matches!(self, DType::F32 | DType::F64)
+    }
+
+    pub fn is_integer(self) -> bool {
+        matches!(self, DType::I32 | DType::I64)
+    }
+
+    /// 多个参数运算结果的类型, 取顺序 Bool < I32 < I64 < F32 < F64 中最大的
+    pub fn promote<I: IntoIterator<Item = DType>>(all: I) -> Option<DType> {
+        all.into_iter().max()
+    }
+}
+
+impl Display for DType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DType::Bool => "bool",
+            DType::I32 => "i32",
+            DType::I64 => "i64",
+            DType::F32 => "f32",
+            DType::F64 => "f64",
+        })
+    }
+}
+
+#[test]
+fn test() {
+    use crate::tensor::Tensor;
+    use std::sync::Arc;
+
+    let a = Tensor::mask([3], Arc::new(vec![true, true, false]));
+    let b = Tensor::mask([3], Arc::new(vec![true, false, false]));
+    let c = &a + &b;
+    assert_eq!(c.dtype(), DType::Bool);
+    assert_eq!(c.compute().unwrap().as_slice(), [1.0, 1.0, 0.0]);
+
+    let a = Tensor::indices([3], Arc::new(vec![7, -7, 6]));
+    let b = Tensor::indices([3], Arc::new(vec![2, 2, 4]));
+    let c = &a / &b;
+    assert_eq!(c.dtype(), DType::I64);
+    assert_eq!(c.compute().unwrap().as_slice(), [3.0, -3.0, 1.0]);
+
+    // 与浮点混合时为浮点
+    let c = &a / Tensor::constant([3], Arc::new(vec![2.0; 3]));
+    assert_eq!(c.dtype(), DType::F32);
+    assert_eq!(c.compute().unwrap().as_slice(), [3.5, -3.5, 3.0]);
+
+    let x = Tensor::constant([1], Arc::new(vec![2147483648.0])).cast(DType::I32);
+    assert_eq!(x.compute().unwrap().as_slice(), [-2147483648.0]);
+}
@@ -0,0 +1,159 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::sync::Arc;
+
+use crate::core::constant::ConstantData;
+
+/// CPU 计算所用的元素类型
+pub trait Element:
+    Copy
+    + Debug
+    + Default
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + Sum
+    + Send
+    + Sync
+    + 'static
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn from_f32(x: f32) -> Self;
+    fn from_f64(x: f64) -> Self;
+    fn to_f32(self) -> f32;
+    fn to_f64(self) -> f64;
+
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+    fn powf(self, x: Self) -> Self;
+    fn max(self, x: Self) -> Self;
+    fn min(self, x: Self) -> Self;
+    fn atan2(self, x: Self) -> Self;
+    fn hypot(self, x: Self) -> Self;
+    fn is_sign_positive(self) -> bool;
+    /// 全序比较, 正的 NaN 最大, 负的 NaN 最小, -0 小于 +0
+    fn total_cmp(&self, x: &Self) -> Ordering;
+
+    /// 作为下标时的整数值, 不是有限的整数时为 `None`
+    fn to_index(self) -> Option<i64> {
+        let x = self.to_f64();
+        (x.is_finite() && x.fract() == 0.0).then_some(x as i64)
+    }
+
+    fn from_f32_slice(data: &[f32]) -> Vec<Self> {
+        data.iter().map(|&x| Self::from_f32(x)).collect()
+    }
+
+    /// 取出常量数据, 类型相同时不复制
+    fn from_data(data: &ConstantData) -> Arc<Vec<Self>>;
+    fn into_data(data: Arc<Vec<Self>>) -> ConstantData;
+}
+
+macro_rules! impl_element {
+    ($t:ident, $own:ident, $other:ident) => {
+        impl Element for $t {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+
+            fn from_f32(x: f32) -> Self {
+                x as $t
+            }
+
+            fn from_f64(x: f64) -> Self {
+                x as $t
+            }
+
+            fn to_f32(self) -> f32 {
+                self as f32
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn sin(self) -> Self {
+                $t::sin(self)
+            }
+
+            fn cos(self) -> Self {
+                $t::cos(self)
+            }
+
+            fn exp(self) -> Self {
+                $t::exp(self)
+            }
+
+            fn ln(self) -> Self {
+                $t::ln(self)
+            }
+
+            fn abs(self) -> Self {
+                $t::abs(self)
+            }
+
+            fn signum(self) -> Self {
+                $t::signum(self)
+            }
+
+            fn powf(self, x: Self) -> Self {
+                $t::powf(self, x)
+            }
+
+            fn max(self, x: Self) -> Self {
+                $t::max(self, x)
+            }
+
+            fn min(self, x: Self) -> Self {
+                $t::min(self, x)
+            }
+
+            fn atan2(self, x: Self) -> Self {
+                $t::atan2(self, x)
+            }
+
+            fn hypot(self, x: Self) -> Self {
+                $t::hypot(self, x)
+            }
+
+            fn is_sign_positive(self) -> bool {
+                $t::is_sign_positive(self)
+            }
+
+            fn total_cmp(&self, x: &Self) -> Ordering {
+                $t::total_cmp(self, x)
+            }
+
+            fn from_data(data: &ConstantData) -> Arc<Vec<Self>> {
+                match data {
+                    ConstantData::$own(data) => data.clone(),
+                    ConstantData::$other(data) => Arc::new(data.iter().map(|&x| x as $t).collect()),
+                    ConstantData::I64(data) => Arc::new(data.iter().map(|&x| x as $t).collect()),
+                    ConstantData::Bool(data) => {
+                        Arc::new(data.iter().map(|&x| if x { 1.0 } else { 0.0 }).collect())
+                    }
+                }
+            }
+
+            fn into_data(data: Arc<Vec<Self>>) -> ConstantData {
+                ConstantData::$own(data)
+            }
+        }
+    };
+}
+
+impl_element!(f32, F32, F64);
+impl_element!(f64, F64, F32);
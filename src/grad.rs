@@ -1,24 +1,56 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
 
 use crate::core::add_tensor::AddTensor;
-use crate::tensor::{Tensor, TensorHandle};
+use crate::core::merge_tensor::MergeTensor;
+use crate::tensor::{data_size, Tensor, TensorHandle};
 
 pub struct BackwardGrad {
     table: BTreeMap<TensorHandle, Vec<Tensor>>,
+    /// 只为能到达这些张量的节点求梯度
+    targets: Option<HashSet<TensorHandle>>,
+    reach: HashMap<TensorHandle, bool>,
 }
 
 impl BackwardGrad {
     pub fn new() -> Self {
         Self {
             table: BTreeMap::new(),
+            targets: None,
+            reach: HashMap::new(),
         }
     }
 
+    /// 剪去所有不能到达 `targets` 的路径
+    pub fn new_to(targets: &[Tensor]) -> Self {
+        Self {
+            table: BTreeMap::new(),
+            targets: Some(targets.iter().map(|x| x.clone().into()).collect()),
+            reach: HashMap::new(),
+        }
+    }
+
+    fn reach(&mut self, tensor: &Tensor) -> bool {
+        let Some(targets) = &self.targets else { return true; };
+        if targets.contains(tensor.into()) {
+            return true;
+        }
+        if let Some(&r) = self.reach.get(tensor.into()) {
+            return r;
+        }
+        let r = tensor.arguments().iter().any(|x| self.reach(x));
+        self.reach.insert(tensor.clone().into(), r);
+        r
+    }
+
     pub fn append<S: Into<Tensor>, G: Into<Tensor>>(&mut self, source: S, grad: G) {
         let source = source.into();
         let grad = grad.into();
         assert_eq!(grad.shape(), source.shape());
-        self.table.entry(source.into()).or_default().push(grad);
+        // 整数和布尔张量没有梯度
+        if source.dtype().is_float() && self.reach(&source) {
+            self.table.entry(source.into()).or_default().push(grad);
+        }
     }
 
     pub fn result(self) -> HashMap<TensorHandle, Tensor> {
@@ -33,6 +65,18 @@ impl BackwardGrad {
         }
         output
     }
+
+    /// 依次返回 `targets` 的梯度, 不能到达的目标梯度为零
+    pub fn result_for(self, targets: &[Tensor]) -> Vec<Tensor> {
+        let result = self.result();
+        targets
+            .iter()
+            .map(|t| match result.get(t.into()) {
+                Some(g) => g.clone(),
+                None => Tensor::zero(t.shape()),
+            })
+            .collect()
+    }
 }
 
 pub struct ForwardGrad {
@@ -63,6 +107,11 @@ impl ForwardGrad {
         if let Some(r) = self.table.get(tensor.into()) {
             return r.clone().unwrap();
         }
+        if !tensor.dtype().is_float() {
+            let r = Tensor::zero(tensor.shape());
+            self.table.insert(tensor.clone().into(), Some(r.clone()));
+            return r;
+        }
         self.table.insert(tensor.clone().into(), None);
         let r = tensor.operator().forward_grad(tensor, self);
         let node = self.table.get_mut(tensor.into()).unwrap();
@@ -71,3 +120,109 @@ impl ForwardGrad {
         r
     }
 }
+
+/// 海森矩阵与向量之积 H v, `f` 为标量
+///
+/// 先反向求出 `f` 对 `x` 的梯度, 再沿 `v` 方向前向求导.
+pub fn hvp(f: &Tensor, x: &Tensor, v: &Tensor) -> Tensor {
+    assert_eq!(data_size(f.shape()), 1);
+    assert_eq!(x.shape(), v.shape());
+    let grad = f.back(x);
+    ForwardGrad::new(&[(x.clone(), v.clone())]).compute(&grad)
+}
+
+/// 雅可比矩阵, 形状为 `y.shape() ++ x.shape()`
+///
+/// 对 `y` 的每个元素各做一次反向传播.
+pub fn jacobian(y: &Tensor, x: &Tensor) -> Tensor {
+    let len = data_size(y.shape());
+    let mut rows = Vec::with_capacity(len);
+    for i in 0..len {
+        let mut seed = vec![0.0; len];
+        seed[i] = 1.0;
+        let mut context = BackwardGrad::new_to(std::slice::from_ref(x));
+        context.append(y, Tensor::constant(y.shape(), Arc::new(seed)));
+        let [row] = &context.result_for(std::slice::from_ref(x))[..] else { panic!() };
+        rows.push(row.reshape([1, data_size(x.shape())]));
+    }
+    let shape = y
+        .shape()
+        .iter()
+        .chain(x.shape())
+        .copied()
+        .collect::<Vec<_>>();
+    MergeTensor::merge(rows).reshape(shape)
+}
+
+/// 海森矩阵, `f` 为标量, 形状为 `x.shape() ++ x.shape()`
+pub fn hessian(f: &Tensor, x: &Tensor) -> Tensor {
+    assert_eq!(data_size(f.shape()), 1);
+    jacobian(&f.back(x), x)
+}
+
+#[test]
+fn test() {
+    let a = Tensor::scale(2.0);
+    let b = Tensor::scale(3.0);
+    let c = Tensor::scale(4.0);
+    let d = Tensor::variable([]);
+    let y = &a * &b + &c * &d;
+
+    let mut context = BackwardGrad::new_to(&[a.clone(), b.clone()]);
+    context.append(&y, Tensor::scale(1.0));
+    let result = context.result();
+    assert!(!result.contains_key(<&TensorHandle>::from(&c)));
+    assert!(!result.contains_key(<&TensorHandle>::from(&d)));
+
+    let grads = y.grads(&[a.clone(), b.clone(), Tensor::scale(1.0)]);
+    assert_eq!(grads[0].compute().unwrap().as_slice(), [3.0]);
+    assert_eq!(grads[1].compute().unwrap().as_slice(), [2.0]);
+    assert_eq!(grads[2].compute().unwrap().as_slice(), [0.0]);
+}
+
+#[test]
+fn test_higher_order() {
+    use crate::core::function::Function;
+    use crate::core::sum_scale::SumScale;
+
+    fn assert_close(a: &[f32], b: &[f32]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < 1e-4, "{:?} != {:?}", a, b);
+        }
+    }
+
+    let xs = [0.5, -1.0, 2.0];
+    let x = Tensor::constant([3], Arc::new(xs.to_vec()));
+    let v = Tensor::constant([3], Arc::new(vec![1.0, 2.0, -1.0]));
+
+    // f = sum(x^3), H = diag(6x)
+    let f = SumScale::sum(x.powf(3.0));
+    assert_close(&hvp(&f, &x, &v).compute().unwrap(), &[3.0, -12.0, -12.0]);
+    let h = hessian(&f, &x);
+    assert_eq!(h.shape(), [3, 3]);
+    assert_close(
+        &h.compute().unwrap(),
+        &[3.0, 0.0, 0.0, 0.0, -6.0, 0.0, 0.0, 0.0, 12.0],
+    );
+
+    // f = x0 * x1 + x1 * x2
+    let f = x.get([0]) * x.get([1]) + x.get([1]) * x.get([2]);
+    assert_close(
+        &hessian(&f, &x).compute().unwrap(),
+        &[0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0],
+    );
+
+    // y = sigmoid(x), J = diag(s (1 - s)), f = sum(y) 的 H = diag(s (1 - s) (1 - 2s))
+    let s = xs.map(|x| 1.0 / (1.0 + (-x).exp()));
+    let y = x.apply(Function::Sigmoid);
+    let mut expect = vec![0.0; 9];
+    for i in 0..3 {
+        expect[i * 4] = s[i] * (1.0 - s[i]);
+    }
+    assert_close(&jacobian(&y, &x).compute().unwrap(), &expect);
+    for i in 0..3 {
+        expect[i * 4] = s[i] * (1.0 - s[i]) * (1.0 - 2.0 * s[i]);
+    }
+    assert_close(&hessian(&SumScale::sum(y), &x).compute().unwrap(), &expect);
+}
@@ -0,0 +1,153 @@
+use rand::distributions::Distribution;
+use rand::Rng;
+use rand_distr::{Normal, StandardNormal, Uniform};
+
+use crate::tensor::data_size;
+
+/// 由形状计算 (fan_in, fan_out)
+///
+/// 矩阵按 `matrix_mul` 的约定为 `[in, out]`,
+/// 更高维的形状把 `shape[2..]` 视为感受野.
+pub fn fan_in_out(shape: &[usize]) -> (usize, usize) {
+    match shape {
+        [] => (1, 1),
+        &[n] => (n, n),
+        &[i, o, ref rest @ ..] => {
+            let receptive = data_size(rest);
+            (i * receptive, o * receptive)
+        }
+    }
+}
+
+pub trait Initialize {
+    fn initialize<R: Rng + ?Sized>(&self, shape: &[usize], rng: &mut R) -> Vec<f32>;
+}
+
+impl<D: Distribution<f32>> Initialize for D {
+    fn initialize<R: Rng + ?Sized>(&self, shape: &[usize], rng: &mut R) -> Vec<f32> {
+        let len = data_size(shape);
+        let mut output = Vec::with_capacity(len);
+        for _ in 0..len {
+            output.push(self.sample(rng));
+        }
+        output
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum Initializer {
+    Zeros,
+    Constant(f32),
+    /// 均匀分布 U(low, high)
+    Uniform(f32, f32),
+    /// 正态分布 N(mean, std)
+    Normal(f32, f32),
+    /// U(-a, a), a = sqrt(6 / (fan_in + fan_out))
+    XavierUniform,
+    /// N(0, std), std = sqrt(2 / (fan_in + fan_out))
+    XavierNormal,
+    /// U(-a, a), a = sqrt(6 / fan_in)
+    HeUniform,
+    /// N(0, std), std = sqrt(2 / fan_in)
+    HeNormal,
+    /// 以 `shape[0]` 为行展开成矩阵后的 (半) 正交矩阵, 乘以增益
+    Orthogonal(f32),
+}
+
+impl Initializer {
+    fn orthogonal<R: Rng + ?Sized>(gain: f32, shape: &[usize], rng: &mut R) -> Vec<f32> {
+        let rows = shape.first().copied().unwrap_or(1);
+        let cols = data_size(shape) / rows.max(1);
+        let (n, m) = (rows.max(cols), rows.min(cols));
+
+        // 对 n x m 的高斯矩阵的列做 Gram-Schmidt 正交化
+        let mut q: Vec<Vec<f64>> = Vec::with_capacity(m);
+        while q.len() < m {
+            let mut v = (0..n)
+                .map(|_| rng.sample::<f64, _>(StandardNormal))
+                .collect::<Vec<_>>();
+            for u in &q {
+                let dot = v.iter().zip(u).map(|(a, b)| a * b).sum::<f64>();
+                v.iter_mut().zip(u).for_each(|(a, b)| *a -= dot * b);
+            }
+            let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+            if norm > 1e-6 {
+                v.iter_mut().for_each(|x| *x /= norm);
+                q.push(v);
+            }
+        }
+
+        let mut output = Vec::with_capacity(rows * cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                let (a, b) = if rows >= cols { (j, i) } else { (i, j) };
+                output.push(q[a][b] as f32 * gain);
+            }
+        }
+        output
+    }
+}
+
+impl Initialize for Initializer {
+    fn initialize<R: Rng + ?Sized>(&self, shape: &[usize], rng: &mut R) -> Vec<f32> {
+        let (fan_in, fan_out) = fan_in_out(shape);
+        let (fan_in, fan_out) = (fan_in.max(1) as f32, fan_out.max(1) as f32);
+        match *self {
+            Initializer::Zeros => vec![0.0; data_size(shape)],
+            Initializer::Constant(x) => vec![x; data_size(shape)],
+            Initializer::Uniform(low, high) => Uniform::new(low, high).initialize(shape, rng),
+            Initializer::Normal(mean, std) => {
+                Normal::new(mean, std).unwrap().initialize(shape, rng)
+            }
+            Initializer::XavierUniform => {
+                let a = (6.0 / (fan_in + fan_out)).sqrt();
+                Uniform::new_inclusive(-a, a).initialize(shape, rng)
+            }
+            Initializer::XavierNormal => {
+                let std = (2.0 / (fan_in + fan_out)).sqrt();
+                Normal::new(0.0, std).unwrap().initialize(shape, rng)
+            }
+            Initializer::HeUniform => {
+                let a = (6.0 / fan_in).sqrt();
+                Uniform::new_inclusive(-a, a).initialize(shape, rng)
+            }
+            Initializer::HeNormal => {
+                let std = (2.0 / fan_in).sqrt();
+                Normal::new(0.0, std).unwrap().initialize(shape, rng)
+            }
+            Initializer::Orthogonal(gain) => Self::orthogonal(gain, shape, rng),
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    assert_eq!(fan_in_out(&[3, 4]), (3, 4));
+    assert_eq!(fan_in_out(&[3, 4, 2, 2]), (12, 16));
+
+    let rng = &mut SmallRng::seed_from_u64(0);
+    let a = 6.0f32.sqrt() / 10.0;
+    let w = Initializer::HeUniform.initialize(&[100, 10], rng);
+    assert_eq!(w.len(), 1000);
+    assert!(w.iter().all(|x| x.abs() <= a));
+
+    for shape in [[3, 5], [5, 3]] {
+        let q = Initializer::Orthogonal(1.0).initialize(&shape, rng);
+        let [r, c] = shape;
+        let k = r.min(c);
+        for i in 0..k {
+            for j in 0..k {
+                let dot = if r < c {
+                    (0..c).map(|t| q[i * c + t] * q[j * c + t]).sum::<f32>()
+                } else {
+                    (0..r).map(|t| q[t * c + i] * q[t * c + j]).sum::<f32>()
+                };
+                let expect = if i == j { 1.0 } else { 0.0 };
+                assert!((dot - expect).abs() < 1e-5);
+            }
+        }
+    }
+}
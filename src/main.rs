@@ -1,10 +1,15 @@
 use std::error::Error;
 
+pub mod backend;
 pub mod core;
 pub mod cpu;
 pub mod demo;
+pub mod dtype;
+pub mod element;
 pub mod grad;
+pub mod initializer;
 pub mod model_context;
+pub mod nn;
 pub mod tensor;
 pub mod tools;
 pub mod variable_inline;
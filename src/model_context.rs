@@ -1,64 +1,262 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 
-use rand::distributions::Distribution;
 use rand::rngs::SmallRng;
-use rand::{Rng, SeedableRng};
+use rand::SeedableRng;
 use rand_distr::Normal;
 
-use crate::cpu::CpuContext;
-use crate::grad::BackwardGrad;
+use crate::backend::Backend;
+use crate::core::add_tensor::AddTensor;
+use crate::core::assign::Assign;
+use crate::core::index_add::IndexAdd;
+use crate::element::Element;
+use crate::initializer::Initialize;
 use crate::tensor::{data_size, Tensor};
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModelError<E> {
+    /// 后端计算出错
+    Backend(E),
+    /// 稀疏梯度的下标张量的第 `position` 个值 `index` 不在 `0..size` 内
+    IndexOutOfBounds {
+        position: usize,
+        index: i64,
+        size: usize,
+    },
+    /// 稀疏梯度的下标张量的第 `position` 个值 `value` 不是有限的整数
+    InvalidIndex { position: usize, value: f64 },
+}
+
+impl<E: Display> Display for ModelError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelError::Backend(e) => write!(f, "{}", e),
+            ModelError::IndexOutOfBounds {
+                position,
+                index,
+                size,
+            } => write!(
+                f,
+                "the sparse gradient index {} at position {} is out of bounds for size {}",
+                index, position, size
+            ),
+            ModelError::InvalidIndex { position, value } => write!(
+                f,
+                "the sparse gradient index {} at position {} is not an integer",
+                value, position
+            ),
+        }
+    }
+}
+
+impl<E: Debug + Display> Error for ModelError<E> {}
+
+/// 以 `T` 保存参数的模型, `ModelContext::new` 为 f32,
+/// 其他类型用 `ModelContext::<f64>::default()` 创建
 #[derive(Debug)]
-pub struct ModelContext {
+pub struct ModelContext<T: Element = f32> {
     index: usize,
-    variables: Vec<(Tensor, Vec<f32>)>,
+    scope: Vec<String>,
+    names: HashMap<String, usize>,
+    /// 本次构建中已经定义过的参数的序号, `reset` 时清空
+    defined: HashSet<usize>,
+    /// 按名称预先载入, 尚未创建变量的参数
+    pending: HashMap<String, Vec<T>>,
+    variables: Vec<(String, Tensor, Vec<T>)>,
+    /// 不参与梯度下降的参数的序号, 如 BatchNorm 的滑动平均
+    buffers: HashSet<usize>,
+    /// 每次优化后写入参数的值
+    updates: Vec<(Tensor, Tensor)>,
+    /// 更新前把所有梯度的 L2 范数限制在此值以内
+    grad_clip: Option<f32>,
+}
+
+impl<T: Element> Default for ModelContext<T> {
+    fn default() -> Self {
+        Self::new_with(Vec::new())
+    }
 }
 
 impl ModelContext {
     pub fn new() -> Self {
-        Self::new_with(Vec::new())
+        Self::default()
     }
+}
 
-    pub fn new_with(variables: Vec<(Tensor, Vec<f32>)>) -> Self {
-        Self {
+impl<T: Element> ModelContext<T> {
+    pub fn new_with(variables: Vec<(Tensor, Vec<T>)>) -> Self {
+        let mut s = Self {
             index: 0,
-            variables,
+            scope: Vec::new(),
+            names: HashMap::new(),
+            defined: HashSet::new(),
+            pending: HashMap::new(),
+            variables: Vec::with_capacity(variables.len()),
+            buffers: HashSet::new(),
+            updates: Vec::new(),
+            grad_clip: None,
+        };
+        for (i, (var, val)) in variables.into_iter().enumerate() {
+            assert_eq!(data_size(var.shape()), val.len());
+            s.names.insert(anonymous(i), i);
+            s.variables.push((anonymous(i), var, val));
         }
+        s
     }
 
+    /// 按注册顺序编号的匿名参数, 名称为当前作用域下的 `#序号`
     pub fn variable<S: AsRef<[usize]>>(&mut self, shape: S) -> Tensor {
         self.variable_rng(shape, Normal::new(0.0, 0.01).unwrap())
     }
 
-    pub fn variable_rng<S: AsRef<[usize]>, D: Distribution<f32>>(
+    pub fn variable_rng<S: AsRef<[usize]>, I: Initialize>(&mut self, shape: S, init: I) -> Tensor {
+        let name = self.full_name(&anonymous(self.index));
+        self.index += 1;
+        self.define(name, shape.as_ref(), init)
+    }
+
+    /// 以当前作用域下的名称创建参数, 见 `reset`
+    pub fn named_variable<S: AsRef<[usize]>>(&mut self, name: &str, shape: S) -> Tensor {
+        self.named_variable_rng(name, shape, Normal::new(0.0, 0.01).unwrap())
+    }
+
+    pub fn named_variable_rng<S: AsRef<[usize]>, I: Initialize>(
+        &mut self,
+        name: &str,
+        shape: S,
+        init: I,
+    ) -> Tensor {
+        assert!(valid_name(name), "invalid parameter name {:?}", name);
+        let name = self.full_name(name);
+        self.define(name, shape.as_ref(), init)
+    }
+
+    /// 以当前作用域下的名称创建不参与梯度下降的参数, 通过 `add_update` 更新
+    pub fn named_buffer<S: AsRef<[usize]>, I: Initialize>(
         &mut self,
+        name: &str,
         shape: S,
-        rng: D,
+        init: I,
     ) -> Tensor {
-        if self.index < self.variables.len() {
-            let var = self.variables[self.index].0.clone();
-            assert_eq!(var.shape(), shape.as_ref());
-            self.index += 1;
+        let var = self.named_variable_rng(name, shape, init);
+        let i = self.index_of(&var).unwrap();
+        self.buffers.insert(i);
+        var
+    }
+
+    /// 每次 `optimization` 后把 `value` 在更新前参数上的值写入 `var`
+    pub fn add_update<V: Into<Tensor>>(&mut self, var: &Tensor, value: V) {
+        let value = value.into();
+        assert_eq!(var.shape(), value.shape());
+        assert!(self.index_of(var).is_some());
+        self.updates.push((var.clone(), value));
+    }
+
+    fn index_of(&self, var: &Tensor) -> Option<usize> {
+        self.variables.iter().position(|(_, v, _)| v.same(var))
+    }
+
+    fn define<I: Initialize>(&mut self, name: String, shape: &[usize], init: I) -> Tensor {
+        if let Some(&i) = self.names.get(&name) {
+            assert!(
+                self.defined.insert(i),
+                "the parameter {} is defined twice, call `reset` before rebuilding the model",
+                name
+            );
+            let var = self.variables[i].1.clone();
+            assert_eq!(
+                var.shape(),
+                shape,
+                "the parameter {} was defined with another shape",
+                name
+            );
             var
         } else {
-            let len = data_size(shape.as_ref());
+            let len = data_size(shape);
+            let value = match self.pending.remove(&name) {
+                Some(value) => value,
+                None => T::from_f32_slice(&init.initialize(shape, &mut SmallRng::from_entropy())),
+            };
+            assert_eq!(
+                value.len(),
+                len,
+                "the loaded parameter {} does not match the shape {:?}",
+                name,
+                shape
+            );
             let var = Tensor::variable(shape);
-            let value = SmallRng::from_entropy()
-                .sample_iter(rng)
-                .take(len)
-                .collect::<Vec<_>>();
-            assert_eq!(value.len(), len);
-            self.variables.push((var.clone(), value));
-            self.index += 1;
+            self.defined.insert(self.variables.len());
+            self.names.insert(name.clone(), self.variables.len());
+            self.variables.push((name, var.clone(), value));
             var
         }
     }
 
-    pub fn set_value(&mut self, var: &Tensor, v: Vec<f32>) -> bool {
+    fn full_name(&self, name: &str) -> String {
+        let mut full = String::new();
+        for s in &self.scope {
+            full.push_str(s);
+            full.push('.');
+        }
+        full.push_str(name);
+        full
+    }
+
+    /// 进入子作用域, 返回值析构时退出
+    ///
+    /// ```ignore
+    /// let w = model.scope("fc1").named_variable("weight", [784, 150]);
+    /// ```
+    pub fn scope(&mut self, name: &str) -> ModelScope<'_, T> {
+        assert!(valid_name(name), "invalid scope name {:?}", name);
+        self.scope.push(name.to_string());
+        ModelScope { model: self }
+    }
+
+    /// 以完整名称遍历所有参数
+    pub fn parameters(&self) -> impl Iterator<Item = (&str, &Tensor, &[T])> {
+        self.variables
+            .iter()
+            .map(|(name, var, val)| (name.as_str(), var, val.as_slice()))
+    }
+
+    pub fn get(&self, name: &str) -> Option<(&Tensor, &[T])> {
+        let &i = self.names.get(name)?;
+        let (_, var, val) = &self.variables[i];
+        Some((var, val.as_slice()))
+    }
+
+    pub fn set_named_value(&mut self, name: &str, v: Vec<T>) -> bool {
+        let Some(&i) = self.names.get(name) else { return false; };
+        let (_, var, value) = &mut self.variables[i];
+        assert_eq!(data_size(var.shape()), v.len());
+        *value = v;
+        true
+    }
+
+    /// 按完整名称载入参数, 尚未创建的参数在创建时使用载入的值, 见 `unmatched_names`
+    pub fn load_named<I: IntoIterator<Item = (String, Vec<T>)>>(&mut self, values: I) {
+        for (name, value) in values {
+            if !self.set_named_value(&name, value.clone()) {
+                self.pending.insert(name, value);
+            }
+        }
+    }
+
+    /// 载入后还没有对应参数的名称, 构建完模型后仍不为空说明名称不匹配
+    pub fn unmatched_names(&self) -> Vec<&str> {
+        let mut names = self.pending.keys().map(|x| x.as_str()).collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    pub fn set_value(&mut self, var: &Tensor, v: Vec<T>) -> bool {
         assert_eq!(data_size(var.shape()), v.len());
         assert!(var.is_variable());
-        for (variable, value) in &mut self.variables {
+        for (_, variable, value) in &mut self.variables {
             if variable.same(var) {
                 *value = v;
                 return true;
@@ -67,36 +265,292 @@ impl ModelContext {
         false
     }
 
+    /// 见 `Tensor::clip_by_global_norm`, `None` 为不限制
+    pub fn set_grad_clip(&mut self, max_norm: Option<f32>) {
+        self.grad_clip = max_norm;
+    }
+
+    /// 开始重新构建模型: 匿名参数重新编号, 之后定义的参数取回同名的已有参数
+    ///
+    /// 两次 `reset` 之间重复定义同一名称的参数会 panic, 以免两层意外地共享参数.
     pub fn reset(&mut self) {
         self.index = 0;
+        self.defined.clear();
     }
 
-    pub fn load_to(&self, context: &mut CpuContext) {
-        for (var, val) in &self.variables {
+    pub fn load_to<B: Backend<Element = T>>(&self, context: &mut B) {
+        for (_, var, val) in &self.variables {
             context.input(var, Arc::new(val.clone()));
         }
     }
 
-    pub fn optimization(
+    pub fn optimization<B: Backend<Element = T>>(
         &mut self,
-        context: &mut CpuContext,
+        context: &mut B,
         target: &Tensor,
-        rate: f32,
-    ) -> Result<(), ()> {
-        let mut back = BackwardGrad::new();
-        back.append(target, Tensor::scale(1.0));
-        let back = back.result();
+        rate: T,
+    ) -> Result<(), ModelError<B::Error>> {
+        let trainable = (0..self.variables.len())
+            .filter(|i| !self.buffers.contains(i))
+            .collect::<Vec<_>>();
+        let variables = trainable
+            .iter()
+            .map(|&i| self.variables[i].1.clone())
+            .collect::<Vec<_>>();
+        let mut grads = target.grads(&variables);
+        if let Some(max_norm) = self.grad_clip {
+            grads = Tensor::clip_by_global_norm(&grads, max_norm);
+        }
         self.load_to(context);
-        for (var, val) in &mut self.variables {
-            let len = val.len();
-            if let Some(b) = back.get(var.as_ref().into()) {
-                let g = context.compute(b)?;
-                let g = g.as_slice();
-                for i in 0..len {
-                    val[i] -= g[i] * rate;
+        let mut updates = Vec::with_capacity(self.updates.len());
+        for (var, value) in &self.updates {
+            let value = value.compute_on(context).map_err(ModelError::Backend)?;
+            updates.push((self.index_of(var).unwrap(), value));
+        }
+        // 先算出所有梯度, 出错时不修改任何参数
+        let mut steps = Vec::with_capacity(grads.len());
+        for b in &grads {
+            let Some(parts) = sparse_rows(b) else {
+                let grad = b.compute_on(context).map_err(ModelError::Backend)?;
+                steps.push((None, vec![grad]));
+                continue;
+            };
+            let mut rows = Vec::new();
+            let mut sources = Vec::with_capacity(parts.len());
+            for (indices, source) in parts {
+                let indices = indices.compute_on(context).map_err(ModelError::Backend)?;
+                for (position, &value) in indices.iter().enumerate() {
+                    let Some(index) = value.to_index() else {
+                        return Err(ModelError::InvalidIndex {
+                            position,
+                            value: value.to_f64(),
+                        });
+                    };
+                    let size = b.shape()[0];
+                    if index < 0 || index as usize >= size {
+                        return Err(ModelError::IndexOutOfBounds {
+                            position,
+                            index,
+                            size,
+                        });
+                    }
+                    rows.push(index as usize);
                 }
+                sources.push(source.compute_on(context).map_err(ModelError::Backend)?);
             }
+            steps.push((Some(rows), sources));
+        }
+        for (&i, (rows, g)) in trainable.iter().zip(steps) {
+            let (_, var, val) = &mut self.variables[i];
+            let mut g = g.iter().flat_map(|x| x.iter());
+            match rows {
+                // 稀疏梯度只更新用到的行
+                Some(rows) => {
+                    let dim = data_size(&var.shape()[1..]);
+                    for row in rows {
+                        for (v, g) in val[row * dim..(row + 1) * dim].iter_mut().zip(&mut g) {
+                            *v -= *g * rate;
+                        }
+                    }
+                }
+                None => {
+                    for (v, g) in val.iter_mut().zip(g) {
+                        *v -= *g * rate;
+                    }
+                }
+            }
+        }
+        for (i, value) in updates {
+            self.variables[i].2 = value.to_vec();
         }
         Ok(())
     }
 }
+
+/// 匿名参数的名称, 以 `#` 开头, 不会与具名参数冲突
+fn anonymous(index: usize) -> String {
+    format!("#{}", index)
+}
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains('.') && !name.starts_with('#')
+}
+
+/// 梯度只由若干沿第 0 维的 `IndexAdd` 相加而成时, 依次返回其 (下标, 来源)
+///
+/// 这时只需更新用到的行. 梯度裁剪后的梯度不是这种形式, 按稠密梯度更新.
+fn sparse_rows(grad: &Tensor) -> Option<Vec<(Tensor, Tensor)>> {
+    let operator = grad.operator();
+    if operator.cast_to::<Assign>().is_some() || operator.cast_to::<AddTensor>().is_some() {
+        let mut parts = Vec::new();
+        for arg in grad.arguments() {
+            parts.extend(sparse_rows(arg)?);
+        }
+        return Some(parts);
+    }
+    match operator.cast_to::<IndexAdd>() {
+        Some(add) if add.axis() == 0 => {
+            let [indices, source] = grad.arguments() else { panic!() };
+            Some(vec![(indices.clone(), source.clone())])
+        }
+        _ => None,
+    }
+}
+
+pub struct ModelScope<'s, T: Element = f32> {
+    model: &'s mut ModelContext<T>,
+}
+
+impl<T: Element> Deref for ModelScope<'_, T> {
+    type Target = ModelContext<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.model
+    }
+}
+
+impl<T: Element> DerefMut for ModelScope<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.model
+    }
+}
+
+impl<T: Element> Drop for ModelScope<'_, T> {
+    fn drop(&mut self) {
+        self.model.scope.pop();
+    }
+}
+
+#[test]
+fn test() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut model = ModelContext::new();
+    let w = model.scope("fc1").named_variable("weight", [3, 2]);
+    let b = model.scope("fc1").named_variable("bias", [2]);
+    let w2 = model.scope("fc2").named_variable("weight", [2, 1]);
+    let x = model_x(&mut model.scope("fc1"));
+    let a = model.scope("fc1").variable([2]);
+    let c = model.variable([1]);
+    assert!(model.get("fc1.bias").unwrap().0.same(&b));
+    assert!(model.get("fc2.weight").unwrap().0.same(&w2));
+    assert!(model.get("weight").is_none());
+    // 匿名参数不会与以序号命名的参数混淆
+    assert!(!model.named_variable("1", [1]).same(&c));
+
+    assert!(model.set_named_value("fc2.weight", vec![1.0, 2.0]));
+    let names = model.parameters().map(|(n, _, _)| n).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "fc1.weight",
+            "fc1.bias",
+            "fc2.weight",
+            "fc1.inner.x",
+            "fc1.#0",
+            "#1",
+            "1"
+        ]
+    );
+
+    // 同一次构建中重复定义会 panic
+    let duplicate = catch_unwind(AssertUnwindSafe(|| {
+        model.scope("fc1").named_variable("weight", [3, 2])
+    }));
+    assert!(duplicate.is_err());
+
+    // `reset` 后重新构建时取回已有的参数
+    model.reset();
+    assert!(model.scope("fc1").named_variable("weight", [3, 2]).same(&w));
+    assert!(model_x(&mut model.scope("fc1")).same(&x));
+    assert!(model.scope("fc1").variable([2]).same(&a));
+
+    // 以不同的顺序构建时按名称取回参数
+    let mut other = ModelContext::new();
+    other.load_named([
+        ("fc2.weight".to_string(), vec![1.0, 2.0]),
+        ("fc3.weight".to_string(), vec![3.0]),
+    ]);
+    other.scope("fc2").named_variable("weight", [2, 1]);
+    assert_eq!(other.get("fc2.weight").unwrap().1, [1.0, 2.0]);
+    assert_eq!(other.unmatched_names(), ["fc3.weight"]);
+
+    fn model_x(model: &mut ModelContext) -> Tensor {
+        model.scope("inner").named_variable("x", [])
+    }
+}
+
+#[test]
+fn grad_clip() {
+    use crate::core::sum_scale::SumScale;
+    use crate::cpu::CpuContext;
+
+    let x = Tensor::variable([2]);
+    let y = Tensor::variable([]);
+    let loss = SumScale::sum(&x * Tensor::constant([2], Arc::new(vec![3.0, 0.0]))) + &y * 4.0;
+    let mut model = ModelContext::new_with(vec![(x, vec![0.0; 2]), (y, vec![0.0])]);
+    model.set_grad_clip(Some(1.0));
+    model
+        .optimization(&mut CpuContext::new(), &loss, 1.0)
+        .unwrap();
+    let values = model
+        .parameters()
+        .map(|(_, _, v)| v.to_vec())
+        .collect::<Vec<_>>();
+    assert_eq!(values, [vec![-0.6, 0.0], vec![-0.8]]);
+}
+
+#[test]
+fn sparse_update() {
+    use crate::core::sum_scale::SumScale;
+    use crate::cpu::CpuContext;
+
+    let table = Tensor::variable([4, 2]);
+    let indices = Tensor::indices([3], Arc::new(vec![1, 3, 1]));
+    let loss = SumScale::sum(table.embedding(&indices));
+    assert!(sparse_rows(&loss.grads(std::slice::from_ref(&table))[0]).is_some());
+
+    let mut model = ModelContext::new_with(vec![(table, vec![0.0; 8])]);
+    model
+        .optimization(&mut CpuContext::new(), &loss, 0.5)
+        .unwrap();
+    assert_eq!(
+        model.parameters().next().unwrap().2,
+        [0.0, 0.0, -1.0, -1.0, 0.0, 0.0, -0.5, -0.5]
+    );
+}
+
+#[test]
+fn sparse_update_out_of_bounds() {
+    use crate::core::sum_scale::SumScale;
+    use crate::cpu::CpuContext;
+
+    let table = Tensor::variable([2, 1]);
+    let indices = Tensor::indices([2], Arc::new(vec![1, 2]));
+    let loss = SumScale::sum(table.embedding(&indices));
+    let mut model = ModelContext::new_with(vec![(table, vec![0.0; 2])]);
+    assert_eq!(
+        model.optimization(&mut CpuContext::new(), &loss, 0.5),
+        Err(ModelError::IndexOutOfBounds {
+            position: 1,
+            index: 2,
+            size: 2
+        })
+    );
+    assert_eq!(model.parameters().next().unwrap().2, [0.0, 0.0]);
+}
+
+#[test]
+fn f64_update() {
+    use crate::core::function::Function;
+    use crate::cpu::CpuContext;
+
+    let x = Tensor::variable([]);
+    let loss = x.apply(Function::Mul(0.1));
+    let mut model = ModelContext::<f64>::new_with(vec![(x, vec![1.0])]);
+    model
+        .optimization(&mut CpuContext::<f64>::default(), &loss, 1e-9)
+        .unwrap();
+    // 步长小于 f32 在 1 附近的精度, 系数 0.1 也不经过 f32
+    assert_eq!(model.parameters().next().unwrap().2, [1.0 - 0.1 * 1e-9]);
+}
@@ -0,0 +1,90 @@
+use crate::core::matrix_mul::MatrixMul;
+use crate::element::Element;
+use crate::initializer::Initializer;
+use crate::model_context::ModelContext;
+use crate::nn::linear::Linear;
+use crate::nn::{repeat_rows, softmax, Layer};
+use crate::tensor::Tensor;
+
+struct Head {
+    query: Linear,
+    key: Linear,
+    value: Linear,
+    output: Linear,
+}
+
+/// 多头自注意力, 输入输出均为 `[seq, dim]`
+///
+/// 第 i 个头的参数位于作用域 `"head{i}"` 下. 各头的输出分别乘以
+/// 输出矩阵的对应行块后相加, 与拼接后再做投影等价.
+pub struct MultiHeadAttention {
+    heads: Vec<Head>,
+    bias: Tensor,
+    head_dim: usize,
+}
+
+impl MultiHeadAttention {
+    pub fn new<T: Element>(model: &mut ModelContext<T>, dim: usize, heads: usize) -> Self {
+        assert!(heads > 0 && dim.is_multiple_of(heads));
+        let head_dim = dim / heads;
+        let heads = (0..heads)
+            .map(|i| {
+                let mut head = model.scope(&format!("head{}", i));
+                let query = Linear::new_no_bias(&mut head.scope("query"), dim, head_dim);
+                let key = Linear::new_no_bias(&mut head.scope("key"), dim, head_dim);
+                let value = Linear::new_no_bias(&mut head.scope("value"), dim, head_dim);
+                let output = Linear::new_no_bias(&mut head.scope("output"), head_dim, dim);
+                Head {
+                    query,
+                    key,
+                    value,
+                    output,
+                }
+            })
+            .collect();
+        let bias = model.named_variable_rng("bias", [dim], Initializer::Zeros);
+        Self {
+            heads,
+            bias,
+            head_dim,
+        }
+    }
+
+    /// `query` 为 `[seq, dim]`, `memory` 为 `[len, dim]`
+    pub fn attend(&self, query: &Tensor, memory: &Tensor) -> Tensor {
+        let &[seq, _] = query.shape() else { panic!() };
+        let scale = 1.0 / (self.head_dim as f32).sqrt();
+        let mut output = repeat_rows(&self.bias, seq);
+        for head in &self.heads {
+            let q = head.query.forward(query);
+            let k = head.key.forward(memory);
+            let v = head.value.forward(memory);
+            // [seq, len]
+            let score = MatrixMul::MulNT.apply(q, k) * scale;
+            let y = softmax(&score).matrix_mul(v);
+            output = output + head.output.forward(&y);
+        }
+        output
+    }
+}
+
+impl Layer for MultiHeadAttention {
+    fn forward(&self, input: &Tensor) -> Tensor {
+        self.attend(input, input)
+    }
+}
+
+#[test]
+fn test() {
+    let mut model = ModelContext::new();
+    let attention = MultiHeadAttention::new(&mut model.scope("attention"), 4, 2);
+    assert_eq!(model.parameters().count(), 9);
+    assert!(model.get("attention.head1.query.weight").is_some());
+
+    let x = Tensor::constant([3, 4], std::sync::Arc::new(vec![0.1; 12]));
+    let y = attention.forward(&x);
+    assert_eq!(y.shape(), [3, 4]);
+    let mut context = crate::cpu::CpuContext::new();
+    model.load_to(&mut context);
+    assert_eq!(context.compute(&y).unwrap().len(), 12);
+}
@@ -0,0 +1,135 @@
+use crate::core::select::Select;
+use crate::core::strided_slice::SliceSpec;
+use crate::core::training::Training;
+use crate::element::Element;
+use crate::initializer::Initializer;
+use crate::model_context::ModelContext;
+use crate::nn::{expand_axes, Layer};
+use crate::tensor::{data_size, Tensor};
+
+/// 以第 1 维为通道, 在其余各维上归一化, 输入为 `[batch, channels, ..]`
+///
+/// 后端处于训练模式 (见 `CpuContext::set_training`) 时使用当前批次的统计量,
+/// 否则使用滑动平均. 滑动平均保存在 `ModelContext` 中, 不参与梯度下降,
+/// 只有用 `add_updates` 登记后才会在每次 `optimization` 时更新, 否则一直是初始值.
+/// 方差的滑动平均使用无偏估计, 归一化本身使用有偏估计.
+#[derive(Debug, Clone)]
+pub struct BatchNorm {
+    gain: Tensor,
+    bias: Tensor,
+    running_mean: Tensor,
+    running_var: Tensor,
+    eps: f32,
+    momentum: f32,
+}
+
+impl BatchNorm {
+    pub fn new<T: Element>(model: &mut ModelContext<T>, channels: usize) -> Self {
+        let gain = model.named_variable_rng("gain", [channels], Initializer::Constant(1.0));
+        let bias = model.named_variable_rng("bias", [channels], Initializer::Zeros);
+        let running_mean = model.named_buffer("running_mean", [channels], Initializer::Zeros);
+        let running_var = model.named_buffer("running_var", [channels], Initializer::Constant(1.0));
+        Self {
+            gain,
+            bias,
+            running_mean,
+            running_var,
+            eps: 1e-5,
+            momentum: 0.1,
+        }
+    }
+
+    /// 登记以 `input` 的批次统计量更新滑动平均
+    pub fn add_updates<T: Element>(&self, model: &mut ModelContext<T>, input: &Tensor) {
+        let axes = self.axes(input.shape());
+        let specs = (0..input.shape().len())
+            .map(|i| {
+                if axes.contains(&i) {
+                    SliceSpec::Index(0)
+                } else {
+                    SliceSpec::all()
+                }
+            })
+            .collect::<Vec<_>>();
+        let input = input.detach();
+        let mean = input.group_mean(&axes);
+        let n = data_size(input.shape()) / input.shape()[1];
+        let correction = n as f32 / (n.max(2) - 1) as f32;
+        let var = (&input - &mean).powf(2.0).group_mean(&axes) * correction;
+        let m = self.momentum;
+        for (running, batch) in [(&self.running_mean, mean), (&self.running_var, var)] {
+            model.add_update(running, running * (1.0 - m) + batch.slice(&specs) * m);
+        }
+    }
+
+    fn axes(&self, shape: &[usize]) -> Vec<usize> {
+        let &[channels] = self.gain.shape() else { panic!() };
+        assert!(
+            shape.len() >= 2 && shape[1] == channels,
+            "the batch norm input must be [batch, channels, ..]"
+        );
+        (0..shape.len()).filter(|&i| i != 1).collect()
+    }
+}
+
+impl Layer for BatchNorm {
+    fn forward(&self, input: &Tensor) -> Tensor {
+        let shape = input.shape();
+        let axes = self.axes(shape);
+        let batch = input.normalize(&axes, self.eps);
+        let mean = expand_axes(&self.running_mean, shape, &axes);
+        let r = expand_axes(&(&self.running_var + self.eps).powf(-0.5), shape, &axes);
+        let running = (input - mean) * r;
+        let y = Select::tensor(Training::tensor(), batch, running);
+        y * expand_axes(&self.gain, shape, &axes) + expand_axes(&self.bias, shape, &axes)
+    }
+}
+
+#[test]
+fn test() {
+    use crate::core::sum_scale::SumScale;
+    use crate::cpu::CpuContext;
+    use std::sync::Arc;
+
+    let mut model = ModelContext::new();
+    let norm = BatchNorm::new(&mut model, 2);
+    let x = Tensor::constant([2, 2], Arc::new(vec![1.0, 3.0, 3.0, 7.0]));
+    let y = norm.forward(&x);
+    let mut context = CpuContext::new();
+    model.load_to(&mut context);
+    let value = context.compute(&y).unwrap();
+    for (a, b) in value.iter().zip([-1.0, -1.0, 1.0, 1.0]) {
+        assert!((a - b).abs() < 1e-4);
+    }
+
+    // 滑动平均不参与梯度下降
+    norm.add_updates(&mut model, &x);
+    let loss = SumScale::sum(y.clone());
+    model
+        .optimization(&mut CpuContext::new(), &loss, 1.0)
+        .unwrap();
+    let close = |name: &str, expect: [f32; 2]| {
+        let value = model.get(name).unwrap().1;
+        for (a, b) in value.iter().zip(expect) {
+            assert!((a - b).abs() < 1e-5, "{} {:?}", name, value);
+        }
+    };
+    close("running_mean", [0.2, 0.5]);
+    close("running_var", [1.1, 1.7]);
+
+    let mut context = CpuContext::new();
+    context.set_training(false);
+    model.load_to(&mut context);
+    let value = context.compute(&y).unwrap();
+    let (s0, s1) = ((1.1f32 + 1e-5).sqrt(), (1.7f32 + 1e-5).sqrt());
+    let bias = model.get("bias").unwrap().1;
+    let expect = [
+        0.8 / s0 + bias[0],
+        2.5 / s1 + bias[1],
+        2.8 / s0 + bias[0],
+        6.5 / s1 + bias[1],
+    ];
+    for (a, b) in value.iter().zip(expect) {
+        assert!((a - b).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::core::matrix_mul::MatrixMul;
+use crate::core::slice_tensor::SliceTensor;
+use crate::element::Element;
+use crate::initializer::Initializer;
+use crate::model_context::ModelContext;
+use crate::nn::{repeat_cols, Layer};
+use crate::tensor::Tensor;
+
+/// 二维卷积, 输入为 `[in_channels, h, w]`, 输出为 `[out_channels, oh, ow]`
+///
+/// 卷积核保存为 `[in_channels * kh * kw, out_channels]` 的矩阵,
+/// 输入经 im2col 展开后与之相乘.
+#[derive(Debug, Clone)]
+pub struct Conv2d {
+    weight: Tensor,
+    bias: Tensor,
+    in_channels: usize,
+    kernel: [usize; 2],
+    stride: [usize; 2],
+}
+
+impl Conv2d {
+    pub fn new<T: Element>(
+        model: &mut ModelContext<T>,
+        in_channels: usize,
+        out_channels: usize,
+        kernel: [usize; 2],
+        stride: [usize; 2],
+    ) -> Self {
+        assert!(kernel[0] > 0 && kernel[1] > 0);
+        assert!(stride[0] > 0 && stride[1] > 0);
+        let weight = model.named_variable_rng(
+            "weight",
+            [in_channels * kernel[0] * kernel[1], out_channels],
+            Initializer::HeNormal,
+        );
+        let bias = model.named_variable_rng("bias", [out_channels], Initializer::Zeros);
+        Self {
+            weight,
+            bias,
+            in_channels,
+            kernel,
+            stride,
+        }
+    }
+
+    pub fn output_size(&self, h: usize, w: usize) -> [usize; 2] {
+        let [kh, kw] = self.kernel;
+        let [sh, sw] = self.stride;
+        assert!(h >= kh && w >= kw);
+        [(h - kh) / sh + 1, (w - kw) / sw + 1]
+    }
+
+    /// 展开为 `[oh * ow, in_channels * kh * kw]`
+    fn im2col(&self, input: &Tensor) -> Tensor {
+        let &[c, h, w] = input.shape() else { panic!() };
+        let [kh, kw] = self.kernel;
+        let [sh, sw] = self.stride;
+        let [oh, ow] = self.output_size(h, w);
+
+        let mut rows = Vec::with_capacity(oh * ow * c * kh);
+        for oy in 0..oh {
+            for ox in 0..ow {
+                for ci in 0..c {
+                    for ky in 0..kh {
+                        let from = ci * h * w + (oy * sh + ky) * w + ox * sw;
+                        rows.push(SliceTensor::tensor(input.clone(), from, vec![kw]));
+                    }
+                }
+            }
+        }
+        Tensor::merge(rows).reshape([oh * ow, c * kh * kw])
+    }
+}
+
+impl Layer for Conv2d {
+    fn forward(&self, input: &Tensor) -> Tensor {
+        let &[c, h, w] = input.shape() else { panic!("the conv2d input must be [c, h, w]") };
+        assert_eq!(c, self.in_channels);
+        let &[_, out_channels] = self.weight.shape() else { panic!() };
+        let [oh, ow] = self.output_size(h, w);
+
+        let cols = self.im2col(input);
+        // [out_channels, oh * ow]
+        let y = MatrixMul::MulTT.apply(self.weight.clone(), cols);
+        let bias = repeat_cols(&self.bias.reshape([out_channels, 1]), oh * ow);
+        (y + bias).reshape([out_channels, oh, ow])
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let mut model = ModelContext::new();
+    let conv = Conv2d::new(&mut model, 1, 2, [2, 2], [1, 1]);
+    // 第一个输出通道求和, 第二个取左上角
+    model.set_named_value("weight", vec![1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0]);
+    model.set_named_value("bias", vec![0.0, 10.0]);
+
+    let x = Tensor::constant(
+        [1, 3, 3],
+        Arc::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]),
+    );
+    let y = conv.forward(&x);
+    assert_eq!(y.shape(), [2, 2, 2]);
+    let mut context = crate::cpu::CpuContext::new();
+    model.load_to(&mut context);
+    assert_eq!(
+        context.compute(&y).unwrap().as_slice(),
+        [12.0, 16.0, 24.0, 28.0, 11.0, 12.0, 14.0, 15.0]
+    );
+}
@@ -0,0 +1,49 @@
+use crate::nn::Layer;
+use crate::tensor::Tensor;
+
+/// 以概率 `p` 置零并把其余元素放大 `1 / (1 - p)`
+///
+/// 掩码在后端每次计算时重新抽取, 图可以重复使用. 后端不在训练模式时
+/// (见 `CpuContext::set_training`) 为恒等变换.
+#[derive(Debug, Clone)]
+pub struct Dropout {
+    p: f32,
+}
+
+impl Dropout {
+    pub fn new(p: f32) -> Self {
+        assert!((0.0..1.0).contains(&p));
+        Self { p }
+    }
+}
+
+impl Layer for Dropout {
+    fn forward(&self, input: &Tensor) -> Tensor {
+        if self.p == 0.0 {
+            return input.clone();
+        }
+        input.dropout(self.p)
+    }
+}
+
+#[test]
+fn test() {
+    use crate::cpu::CpuContext;
+    use std::sync::Arc;
+
+    let x = Tensor::constant([100], Arc::new(vec![2.0; 100]));
+    assert!(Dropout::new(0.0).forward(&x).same(&x));
+
+    let y = Dropout::new(0.75).forward(&x);
+    let mut context = CpuContext::new();
+    context.set_seed(3);
+    let value = context.compute(&y).unwrap();
+    let kept = value.iter().filter(|&&v| v != 0.0).count();
+    assert!((10..40).contains(&kept));
+    // 保留的元素放大 1 / (1 - p)
+    assert!(value.iter().all(|&v| v == 0.0 || v == 8.0));
+
+    let mut context = CpuContext::new();
+    context.set_training(false);
+    assert_eq!(context.compute(&y).unwrap().as_slice(), [2.0; 100]);
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use crate::element::Element;
+use crate::initializer::Initializer;
+use crate::model_context::ModelContext;
+use crate::nn::Layer;
+use crate::tensor::Tensor;
+
+/// `[vocab, dim]` 的查找表
+#[derive(Debug, Clone)]
+pub struct Embedding {
+    table: Tensor,
+}
+
+impl Embedding {
+    pub fn new<T: Element>(model: &mut ModelContext<T>, vocab: usize, dim: usize) -> Self {
+        let table = model.named_variable_rng("table", [vocab, dim], Initializer::Normal(0.0, 1.0));
+        Self { table }
+    }
+
+    pub fn table(&self) -> &Tensor {
+        &self.table
+    }
+
+    /// 查找 `index` 中的各项, 输出 `[index.len(), dim]`
+    pub fn lookup(&self, index: &[usize]) -> Tensor {
+        let data = index.iter().map(|&i| i as i64).collect();
+        self.forward(&Tensor::indices([index.len()], Arc::new(data)))
+    }
+}
+
+impl Layer for Embedding {
+    /// 输入为任意形状的下标张量, 输出 `[.., dim]`
+    fn forward(&self, input: &Tensor) -> Tensor {
+        self.table.embedding(input)
+    }
+}
+
+#[test]
+fn test() {
+    let mut model = ModelContext::new();
+    let embedding = Embedding::new(&mut model, 3, 2);
+    model.set_named_value("table", vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    let y = embedding.lookup(&[2, 0, 2]);
+    let mut context = crate::cpu::CpuContext::new();
+    model.load_to(&mut context);
+    assert_eq!(
+        context.compute(&y).unwrap().as_slice(),
+        [5.0, 6.0, 1.0, 2.0, 5.0, 6.0]
+    );
+}
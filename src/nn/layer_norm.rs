@@ -0,0 +1,57 @@
+use crate::element::Element;
+use crate::initializer::Initializer;
+use crate::model_context::ModelContext;
+use crate::nn::{expand_axes, Layer};
+use crate::tensor::Tensor;
+
+/// 对最后一维做归一化, 输入为 `[.., dim]`
+#[derive(Debug, Clone)]
+pub struct LayerNorm {
+    gain: Tensor,
+    bias: Tensor,
+    eps: f32,
+}
+
+impl LayerNorm {
+    pub fn new<T: Element>(model: &mut ModelContext<T>, dim: usize) -> Self {
+        let gain = model.named_variable_rng("gain", [dim], Initializer::Constant(1.0));
+        let bias = model.named_variable_rng("bias", [dim], Initializer::Zeros);
+        Self {
+            gain,
+            bias,
+            eps: 1e-5,
+        }
+    }
+}
+
+impl Layer for LayerNorm {
+    fn forward(&self, input: &Tensor) -> Tensor {
+        let &[dim] = self.gain.shape() else { panic!() };
+        let shape = input.shape();
+        assert_eq!(
+            shape.last(),
+            Some(&dim),
+            "the layer norm input must be [.., dim]"
+        );
+        let last = shape.len() - 1;
+        let batch = (0..last).collect::<Vec<_>>();
+        input.normalize(&[last], self.eps) * expand_axes(&self.gain, shape, &batch)
+            + expand_axes(&self.bias, shape, &batch)
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let mut model = ModelContext::new();
+    let norm = LayerNorm::new(&mut model, 2);
+    let x = Tensor::constant([2, 2], Arc::new(vec![1.0, 3.0, -2.0, 2.0]));
+    let y = norm.forward(&x);
+    let mut context = crate::cpu::CpuContext::new();
+    model.load_to(&mut context);
+    let y = context.compute(&y).unwrap();
+    for (a, b) in y.iter().zip([-1.0, 1.0, -1.0, 1.0]) {
+        assert!((a - b).abs() < 1e-4);
+    }
+}
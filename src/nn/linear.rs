@@ -0,0 +1,85 @@
+use crate::element::Element;
+use crate::initializer::Initializer;
+use crate::model_context::ModelContext;
+use crate::nn::{repeat_rows, Layer};
+use crate::tensor::Tensor;
+
+/// y = x W + b, 输入为 `[in]` 或 `[batch, in]`
+#[derive(Debug, Clone)]
+pub struct Linear {
+    weight: Tensor,
+    bias: Option<Tensor>,
+}
+
+impl Linear {
+    pub fn new<T: Element>(model: &mut ModelContext<T>, input: usize, output: usize) -> Self {
+        let weight = model.named_variable_rng("weight", [input, output], Initializer::HeNormal);
+        let bias = model.named_variable_rng("bias", [output], Initializer::Zeros);
+        Self {
+            weight,
+            bias: Some(bias),
+        }
+    }
+
+    pub fn new_no_bias<T: Element>(
+        model: &mut ModelContext<T>,
+        input: usize,
+        output: usize,
+    ) -> Self {
+        let weight = model.named_variable_rng("weight", [input, output], Initializer::HeNormal);
+        Self { weight, bias: None }
+    }
+
+    pub fn weight(&self) -> &Tensor {
+        &self.weight
+    }
+
+    pub fn bias(&self) -> Option<&Tensor> {
+        self.bias.as_ref()
+    }
+}
+
+impl Layer for Linear {
+    fn forward(&self, input: &Tensor) -> Tensor {
+        let &[_, output] = self.weight.shape() else { panic!() };
+        match *input.shape() {
+            [n] => {
+                let y = input
+                    .reshape([1, n])
+                    .matrix_mul(&self.weight)
+                    .reshape([output]);
+                match &self.bias {
+                    Some(b) => y + b,
+                    None => y,
+                }
+            }
+            [batch, _] => {
+                let y = input.matrix_mul(&self.weight);
+                match &self.bias {
+                    Some(b) => y + repeat_rows(b, batch),
+                    None => y,
+                }
+            }
+            _ => panic!("the linear input must be [in] or [batch, in]"),
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let mut model = ModelContext::new();
+    let linear = Linear::new(&mut model.scope("fc"), 2, 3);
+    model.set_named_value("fc.weight", vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    model.set_named_value("fc.bias", vec![1.0, 1.0, 1.0]);
+
+    let x = Tensor::constant([2, 2], Arc::new(vec![1.0, 0.0, 0.0, 1.0]));
+    let y = linear.forward(&x);
+    let mut context = crate::cpu::CpuContext::new();
+    model.load_to(&mut context);
+    assert_eq!(
+        context.compute(&y).unwrap().as_slice(),
+        [2.0, 3.0, 4.0, 5.0, 6.0, 7.0]
+    );
+}
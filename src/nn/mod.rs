@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use crate::core::function::Function;
+use crate::tensor::{data_size, Tensor};
+
+pub mod attention;
+pub mod batch_norm;
+pub mod conv2d;
+pub mod dropout;
+pub mod embedding;
+pub mod layer_norm;
+pub mod linear;
+pub mod rms_norm;
+pub mod sequential;
+
+pub trait Layer {
+    fn forward(&self, input: &Tensor) -> Tensor;
+}
+
+impl Layer for Function {
+    fn forward(&self, input: &Tensor) -> Tensor {
+        input.apply(*self)
+    }
+}
+
+/// 把 `[n]` 的行向量复制为 `[rows, n]`
+pub fn repeat_rows(row: &Tensor, rows: usize) -> Tensor {
+    let &[n] = row.shape() else { panic!() };
+    Tensor::one([rows, 1]).matrix_mul(row.reshape([1, n]))
+}
+
+/// 把 `[n, 1]` 的列向量复制为 `[n, cols]`
+pub fn repeat_cols(col: &Tensor, cols: usize) -> Tensor {
+    let &[_, 1] = col.shape() else { panic!() };
+    col.matrix_mul(Tensor::one([1, cols]))
+}
+
+/// 把 `shape` 去掉 `axes` 各维形状的 `x` 沿 `axes` 复制为 `shape`
+pub fn expand_axes(x: &Tensor, shape: &[usize], axes: &[usize]) -> Tensor {
+    let mut reduced = shape.to_vec();
+    let mut repeats = vec![1; shape.len()];
+    for &axis in axes {
+        reduced[axis] = 1;
+        repeats[axis] = shape[axis];
+    }
+    assert_eq!(data_size(x.shape()), data_size(&reduced));
+    x.reshape(reduced).tile(&repeats)
+}
+
+/// `[n, m]` 按行求和为 `[n, 1]`
+pub fn sum_rows(x: &Tensor) -> Tensor {
+    let &[_, m] = x.shape() else { panic!() };
+    x.matrix_mul(Tensor::one([m, 1]))
+}
+
+/// 对 `[n, m]` 的每一行求 softmax
+///
+/// 先减去每行的最大值以免溢出, 最大值不传梯度, 不影响结果.
+pub fn softmax(x: &Tensor) -> Tensor {
+    let &[_, m] = x.shape() else { panic!() };
+    let (max, _) = x.topk(1, 1);
+    let e = (x - repeat_cols(&max.detach(), m)).apply(Function::Exp);
+    let sum = repeat_cols(&sum_rows(&e), m);
+    e / sum
+}
+
+/// 以 `[n, classes]` 的 one-hot 常量表示类别序号
+pub fn one_hot(index: &[usize], classes: usize) -> Tensor {
+    let mut data = vec![0.0; index.len() * classes];
+    for (i, &c) in index.iter().enumerate() {
+        assert!(c < classes);
+        data[i * classes + c] = 1.0;
+    }
+    Tensor::constant([index.len(), classes], Arc::new(data))
+}
+
+#[test]
+fn test() {
+    let x = Tensor::constant([2, 2], Arc::new(vec![0.0, 0.0, 1.0, 1.0]));
+    assert_eq!(softmax(&x).compute().unwrap().as_slice(), [0.5; 4]);
+    let x = Tensor::constant([1, 3], Arc::new(vec![1000.0, 1000.0, -1000.0]));
+    assert_eq!(softmax(&x).compute().unwrap().as_slice(), [0.5, 0.5, 0.0]);
+    assert_eq!(
+        one_hot(&[1, 0], 3).compute().unwrap().as_slice(),
+        [0.0, 1.0, 0.0, 1.0, 0.0, 0.0]
+    );
+}
+
+#[test]
+fn grad_check() {
+    use crate::tools::grad_check::gradcheck;
+
+    let x = Tensor::constant([2, 3], Arc::new(vec![1.0, -2.0, 3.0, 0.5, 0.5, -1.0]));
+    let check = gradcheck(|x| softmax(&x[0]), std::slice::from_ref(&x));
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+}
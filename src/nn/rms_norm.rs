@@ -0,0 +1,52 @@
+use crate::element::Element;
+use crate::initializer::Initializer;
+use crate::model_context::ModelContext;
+use crate::nn::{expand_axes, Layer};
+use crate::tensor::Tensor;
+
+/// 以最后一维的均方根归一化, 不减均值也没有偏置, 输入为 `[.., dim]`
+#[derive(Debug, Clone)]
+pub struct RmsNorm {
+    gain: Tensor,
+    eps: f32,
+}
+
+impl RmsNorm {
+    pub fn new<T: Element>(model: &mut ModelContext<T>, dim: usize) -> Self {
+        let gain = model.named_variable_rng("gain", [dim], Initializer::Constant(1.0));
+        Self { gain, eps: 1e-5 }
+    }
+}
+
+impl Layer for RmsNorm {
+    fn forward(&self, input: &Tensor) -> Tensor {
+        let &[dim] = self.gain.shape() else { panic!() };
+        let shape = input.shape();
+        assert_eq!(
+            shape.last(),
+            Some(&dim),
+            "the rms norm input must be [.., dim]"
+        );
+        let last = shape.len() - 1;
+        let batch = (0..last).collect::<Vec<_>>();
+        input.rms_normalize(&[last], self.eps) * expand_axes(&self.gain, shape, &batch)
+    }
+}
+
+#[test]
+fn test() {
+    use std::sync::Arc;
+
+    let mut model = ModelContext::new();
+    let norm = RmsNorm::new(&mut model, 2);
+    model.set_named_value("gain", vec![1.0, 2.0]);
+    let x = Tensor::constant([2, 2], Arc::new(vec![3.0, 4.0, -1.0, 1.0]));
+    let y = norm.forward(&x);
+    let mut context = crate::cpu::CpuContext::new();
+    model.load_to(&mut context);
+    let y = context.compute(&y).unwrap();
+    let r = (12.5f32).sqrt();
+    for (a, b) in y.iter().zip([3.0 / r, 8.0 / r, -1.0, 2.0]) {
+        assert!((a - b).abs() < 1e-4);
+    }
+}
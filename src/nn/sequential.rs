@@ -0,0 +1,89 @@
+use crate::core::function::Function;
+use crate::element::Element;
+use crate::model_context::ModelContext;
+use crate::nn::linear::Linear;
+use crate::nn::Layer;
+use crate::tensor::Tensor;
+
+#[derive(Default)]
+pub struct Sequential {
+    layers: Vec<Box<dyn Layer>>,
+}
+
+impl Sequential {
+    pub fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    pub fn push<L: Layer + 'static>(&mut self, layer: L) {
+        self.layers.push(Box::new(layer));
+    }
+
+    pub fn with<L: Layer + 'static>(mut self, layer: L) -> Self {
+        self.push(layer);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+}
+
+impl Layer for Sequential {
+    fn forward(&self, input: &Tensor) -> Tensor {
+        let mut x = input.clone();
+        for layer in &self.layers {
+            x = layer.forward(&x);
+        }
+        x
+    }
+}
+
+/// 多层感知机, 第 i 层的参数位于作用域 `"{i}"` 下
+pub struct Mlp {
+    layers: Sequential,
+}
+
+impl Mlp {
+    /// `sizes` 依次为输入, 各隐藏层和输出的宽度, 最后一层之后不接激活函数
+    pub fn new<T: Element>(
+        model: &mut ModelContext<T>,
+        sizes: &[usize],
+        activation: Function,
+    ) -> Self {
+        assert!(sizes.len() >= 2);
+        let mut layers = Sequential::new();
+        for (i, w) in sizes.windows(2).enumerate() {
+            layers.push(Linear::new(&mut model.scope(&i.to_string()), w[0], w[1]));
+            if i + 2 < sizes.len() {
+                layers.push(activation);
+            }
+        }
+        Self { layers }
+    }
+}
+
+impl Layer for Mlp {
+    fn forward(&self, input: &Tensor) -> Tensor {
+        self.layers.forward(input)
+    }
+}
+
+#[test]
+fn test() {
+    let mut model = ModelContext::new();
+    let mlp = Mlp::new(&mut model.scope("mlp"), &[4, 3, 2], Function::ReLU);
+    assert_eq!(mlp.layers.len(), 3);
+    let names = model.parameters().map(|(n, _, _)| n).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        ["mlp.0.weight", "mlp.0.bias", "mlp.1.weight", "mlp.1.bias"]
+    );
+
+    let y = mlp.forward(&Tensor::variable([5, 4]));
+    assert_eq!(y.shape(), [5, 2]);
+}
@@ -5,31 +5,93 @@ use std::hash::{Hash, Hasher};
 use std::ops::{Add, Deref, Div, Mul, Neg, Sub};
 use std::sync::Arc;
 
+use crate::backend::Backend;
 use crate::core::add_tensor::AddTensor;
+use crate::core::arg_sort::ArgSort;
 use crate::core::assign::Assign;
-use crate::core::constant::Constant;
+use crate::core::binary::Binary;
+use crate::core::cast::Cast;
+use crate::core::categorical::Categorical;
+use crate::core::compare::Compare;
+use crate::core::concat::Concat;
+use crate::core::constant::{Constant, ConstantData};
+use crate::core::cumulative::Cumulative;
+use crate::core::detach::Detach;
 use crate::core::div_tensor::DivTensor;
+use crate::core::dropout_mask::DropoutMask;
+use crate::core::embedding_lookup::EmbeddingLookup;
 use crate::core::extend_scale::ExtendScale;
+use crate::core::flip::Flip;
 use crate::core::function::Function;
+use crate::core::gather::Gather;
+use crate::core::group_mean::GroupMean;
+use crate::core::index_add::IndexAdd;
+use crate::core::index_select::IndexSelect;
 use crate::core::matrix_mul::MatrixMul;
 use crate::core::merge_tensor::MergeTensor;
 use crate::core::mul_tensor::MulTensor;
+use crate::core::normalize::Normalize;
+use crate::core::pad::{Pad, PadMode};
+use crate::core::random::{Distribution, Random};
 use crate::core::reshape::Reshape;
+use crate::core::scatter_add::ScatterAdd;
 use crate::core::select::Select;
 use crate::core::slice_tensor::SliceTensor;
+use crate::core::straight_through::StraightThrough;
+use crate::core::strided_slice::{SliceSpec, StridedSlice};
 use crate::core::sub_tensor::SubTensor;
+use crate::core::sum_scale::SumScale;
+use crate::core::tile::Tile;
 use crate::core::variable::Variable;
+use crate::core::where_tensor::WhereTensor;
 use crate::core::TensorOperator;
-use crate::cpu::CpuContext;
+use crate::cpu::{CpuContext, CpuError, CpuResult};
+use crate::dtype::DType;
 use crate::grad::BackwardGrad;
 
 pub fn data_size(shape: &[usize]) -> usize {
     shape.iter().product()
 }
 
+/// 形状为 `output` 的张量的每个元素在形状为 `source` 的张量中的位置
+///
+/// `map(axis, i)` 给出第 `axis` 维的下标 `i` 对应的输入下标, `None` 表示不对应任何元素.
+pub fn map_offsets<F: Fn(usize, usize) -> Option<usize>>(
+    output: &[usize],
+    source: &[usize],
+    map: F,
+) -> Vec<Option<usize>> {
+    assert_eq!(output.len(), source.len());
+    let mut offsets = vec![Some(0)];
+    for axis in 0..output.len() {
+        let mut next = Vec::with_capacity(offsets.len() * output[axis]);
+        for &o in &offsets {
+            for i in 0..output[axis] {
+                next.push(match (o, map(axis, i)) {
+                    (Some(o), Some(i)) => Some(o * source[axis] + i),
+                    _ => None,
+                });
+            }
+        }
+        offsets = next;
+    }
+    offsets
+}
+
+/// 以 `axis` 为界把形状分为 (之前的大小, 该维的长度, 之后的大小)
+pub fn axis_split(shape: &[usize], axis: usize) -> (usize, usize, usize) {
+    assert!(axis < shape.len());
+    (
+        data_size(&shape[..axis]),
+        shape[axis],
+        data_size(&shape[axis + 1..]),
+    )
+}
+
 pub struct TensorInner {
     level: u32,
     shape: Vec<usize>,
+    dtype: DType,
     arguments: Vec<Tensor>,
     operator: Box<dyn TensorOperator>,
 }
@@ -39,11 +101,22 @@ impl TensorInner {
         shape: Vec<usize>,
         arguments: Vec<Tensor>,
         operator: Box<dyn TensorOperator>,
+    ) -> Self {
+        let dtype = DType::promote(arguments.iter().map(|x| x.dtype)).unwrap_or(DType::F32);
+        Self::new_with_dtype(shape, arguments, operator, dtype)
+    }
+
+    pub fn new_with_dtype(
+        shape: Vec<usize>,
+        arguments: Vec<Tensor>,
+        operator: Box<dyn TensorOperator>,
+        dtype: DType,
     ) -> Self {
         let level = arguments.iter().map(|x| x.level + 1).max().unwrap_or(0);
         TensorInner {
             level,
             shape,
+            dtype,
             arguments,
             operator,
         }
@@ -53,6 +126,10 @@ impl TensorInner {
         self.shape.as_slice()
     }
 
+    pub fn dtype(&self) -> DType {
+        self.dtype
+    }
+
     pub fn operator(&self) -> &dyn TensorOperator {
         self.operator.deref()
     }
@@ -71,6 +148,7 @@ impl Debug for TensorInner {
         let mut s = f.debug_struct("Tensor");
         s.field("level", &self.level);
         s.field("shape", &self.shape);
+        s.field("dtype", &self.dtype);
         s.field("operator", &self.operator);
         s.field("arguments", &self.arguments);
         s.finish()
@@ -91,26 +169,209 @@ impl Tensor {
         Constant::scale(value)
     }
 
+    pub fn scale_f64(value: f64) -> Self {
+        Constant::scale_f64(value)
+    }
+
     pub fn constant<S: AsRef<[usize]>>(shape: S, data: Arc<Vec<f32>>) -> Self {
         Constant::constant(shape.as_ref().to_vec(), data)
     }
 
+    pub fn constant_f64<S: AsRef<[usize]>>(shape: S, data: Arc<Vec<f64>>) -> Self {
+        Constant::constant_f64(shape.as_ref().to_vec(), data)
+    }
+
+    /// 类型为 `DType::I64` 的常量, 用作标签和下标
+    pub fn indices<S: AsRef<[usize]>>(shape: S, data: Arc<Vec<i64>>) -> Self {
+        Constant::from_data(shape.as_ref().to_vec(), ConstantData::I64(data))
+    }
+
+    /// 类型为 `DType::Bool` 的常量
+    pub fn mask<S: AsRef<[usize]>>(shape: S, data: Arc<Vec<bool>>) -> Self {
+        Constant::from_data(shape.as_ref().to_vec(), ConstantData::Bool(data))
+    }
+
+    pub fn cast(&self, dtype: DType) -> Tensor {
+        Cast::cast(self.clone(), dtype)
+    }
+
     pub fn select<P: AsRef<Tensor>, N: AsRef<Tensor>>(&self, pos: P, neg: N) -> Self {
         Select::tensor(self.clone(), pos.as_ref().clone(), neg.as_ref().clone())
     }
 
+    /// 逐元素选择, `self` 大于零处取 `pos`, 否则取 `neg`, 三者中的标量会被扩展
+    pub fn select_where<P: AsRef<Tensor>, N: AsRef<Tensor>>(&self, pos: P, neg: N) -> Self {
+        let all = [self, pos.as_ref(), neg.as_ref()];
+        let shape = all
+            .iter()
+            .map(|x| x.shape())
+            .find(|x| data_size(x) != 1)
+            .unwrap_or(self.shape())
+            .to_vec();
+        let [mask, pos, neg] = all.map(|x| {
+            if x.shape() != shape.as_slice() && data_size(x.shape()) == 1 {
+                ExtendScale::extend(x.clone(), shape.clone())
+            } else {
+                x.clone()
+            }
+        });
+        WhereTensor::tensor(mask, pos, neg)
+    }
+
+    pub fn gt<O: AsRef<Tensor>>(&self, other: O) -> Tensor {
+        self.compare(Compare::Gt, other.as_ref())
+    }
+
+    pub fn lt<O: AsRef<Tensor>>(&self, other: O) -> Tensor {
+        self.compare(Compare::Lt, other.as_ref())
+    }
+
+    pub fn ge<O: AsRef<Tensor>>(&self, other: O) -> Tensor {
+        self.compare(Compare::Ge, other.as_ref())
+    }
+
+    pub fn le<O: AsRef<Tensor>>(&self, other: O) -> Tensor {
+        self.compare(Compare::Le, other.as_ref())
+    }
+
+    pub fn eq<O: AsRef<Tensor>>(&self, other: O) -> Tensor {
+        self.compare(Compare::Eq, other.as_ref())
+    }
+
+    fn compare(&self, op: Compare, other: &Tensor) -> Tensor {
+        let (a, b) = self.broadcast(other);
+        op.apply(a, b)
+    }
+
+    /// 逐元素取较大者, 相等时梯度给 `self`
+    pub fn maximum<O: AsRef<Tensor>>(&self, other: O) -> Tensor {
+        self.binary(Binary::Maximum, other.as_ref())
+    }
+
+    /// 逐元素取较小者, 相等时梯度给 `self`
+    pub fn minimum<O: AsRef<Tensor>>(&self, other: O) -> Tensor {
+        self.binary(Binary::Minimum, other.as_ref())
+    }
+
+    /// 逐元素求 `self` 的 `other` 次幂
+    pub fn pow<O: AsRef<Tensor>>(&self, other: O) -> Tensor {
+        self.binary(Binary::Pow, other.as_ref())
+    }
+
+    /// 逐元素求 atan2(self, other)
+    pub fn atan2<O: AsRef<Tensor>>(&self, other: O) -> Tensor {
+        self.binary(Binary::Atan2, other.as_ref())
+    }
+
+    /// 逐元素求 sqrt(self^2 + other^2)
+    pub fn hypot<O: AsRef<Tensor>>(&self, other: O) -> Tensor {
+        self.binary(Binary::Hypot, other.as_ref())
+    }
+
+    fn binary(&self, op: Binary, other: &Tensor) -> Tensor {
+        let (a, b) = self.broadcast(other);
+        op.apply(a, b)
+    }
+
+    /// 形状不同时把其中只有一个元素的扩展为另一个的形状
+    ///
+    /// 两者都只有一个元素时 (如 `[1]` 与 `[]`) 扩展维数较少的一个.
+    fn broadcast(&self, other: &Tensor) -> (Tensor, Tensor) {
+        if self.shape() == other.shape() {
+            return (self.clone(), other.clone());
+        }
+        let (a, b) = (data_size(self.shape()), data_size(other.shape()));
+        if a == 1 && (b != 1 || self.shape().len() < other.shape().len()) {
+            (
+                ExtendScale::extend(self.clone(), other.shape().to_vec()),
+                other.clone(),
+            )
+        } else if b == 1 {
+            (
+                self.clone(),
+                ExtendScale::extend(other.clone(), self.shape().to_vec()),
+            )
+        } else {
+            (self.clone(), other.clone())
+        }
+    }
+
     pub fn assign(&self) -> Tensor {
         Assign::assign(self.clone())
     }
 
+    /// 阻断梯度
+    pub fn detach(&self) -> Tensor {
+        Detach::detach(self.clone())
+    }
+
+    /// 值为 `self`, 梯度按 `surrogate` 求
+    pub fn straight_through<S: AsRef<Tensor>>(&self, surrogate: S) -> Tensor {
+        StraightThrough::tensor(self.clone(), surrogate.as_ref().clone())
+    }
+
     pub fn apply(&self, fun: Function) -> Tensor {
         fun.apply(self.clone())
     }
 
-    pub fn powf(&self, p: f32) -> Tensor {
+    pub fn powf(&self, p: f64) -> Tensor {
         self.apply(Function::Pow(p))
     }
 
+    /// [low, high) 上均匀分布的随机张量, 见 `Random`
+    pub fn uniform<S: AsRef<[usize]>>(shape: S, low: f32, high: f32, seed: Option<u64>) -> Tensor {
+        Random::tensor(shape, Distribution::Uniform(low, high), seed)
+    }
+
+    /// 正态分布的随机张量, 见 `Random`
+    pub fn normal<S: AsRef<[usize]>>(shape: S, mean: f32, std: f32, seed: Option<u64>) -> Tensor {
+        Random::tensor(shape, Distribution::Normal(mean, std), seed)
+    }
+
+    /// 以概率 `p` 为 1 的随机张量, 见 `Random`
+    pub fn bernoulli<S: AsRef<[usize]>>(shape: S, p: f32, seed: Option<u64>) -> Tensor {
+        Random::tensor(shape, Distribution::Bernoulli(p), seed)
+    }
+
+    /// 以 `mean + std * eps` 采样, `eps` 为标准正态分布, 梯度传到 `mean` 和 `std`
+    pub fn sample_normal<M: AsRef<Tensor>, D: AsRef<Tensor>>(
+        mean: M,
+        std: D,
+        seed: Option<u64>,
+    ) -> Tensor {
+        let (mean, std) = (mean.as_ref(), std.as_ref());
+        let eps = Tensor::normal(mean.shape(), 0.0, 1.0, seed);
+        mean + std * eps
+    }
+
+    /// 以最后一维为权重抽取类别序号, 见 `Categorical`
+    pub fn categorical(&self, seed: Option<u64>) -> Tensor {
+        Categorical::tensor(self.clone(), seed)
+    }
+
+    /// 以概率 `p` 置零并把其余元素放大 `1 / (1 - p)`, 见 `DropoutMask`
+    pub fn dropout(&self, p: f32) -> Tensor {
+        self * DropoutMask::tensor(self.shape(), p)
+    }
+
+    /// 逐元素限制在 [min, max] 中
+    pub fn clamp(&self, min: f64, max: f64) -> Tensor {
+        self.apply(Function::Clamp(min, max))
+    }
+
+    /// 按所有张量合起来的 L2 范数缩放, 使其不超过 `max_norm`
+    pub fn clip_by_global_norm(all: &[Tensor], max_norm: f32) -> Vec<Tensor> {
+        assert!(max_norm > 0.0);
+        let squares = all
+            .iter()
+            .map(|x| SumScale::sum(x.powf(2.0)))
+            .collect::<Vec<_>>();
+        let norm = AddTensor::tensor(&[], squares).powf(0.5);
+        let max_norm = Tensor::scale(max_norm);
+        let scale = &max_norm / norm.maximum(&max_norm);
+        all.iter().map(|x| x * &scale).collect()
+    }
+
     pub fn matrix_mul<O: AsRef<Tensor>>(&self, other: O) -> Tensor {
         MatrixMul::MulNN.apply(self.clone(), other.as_ref().clone())
     }
@@ -119,6 +380,188 @@ impl Tensor {
         SliceTensor::index(self.clone(), index.as_ref().into())
     }
 
+    /// 沿第 `axis` 维拼接
+    pub fn concat<I: IntoIterator>(all: I, axis: usize) -> Tensor
+    where
+        I::Item: AsRef<Tensor>,
+    {
+        Concat::concat(all.into_iter().map(|x| x.as_ref().clone()).collect(), axis)
+    }
+
+    /// 在第 `axis` 维插入新维后拼接
+    pub fn stack<I: IntoIterator>(all: I, axis: usize) -> Tensor
+    where
+        I::Item: AsRef<Tensor>,
+    {
+        let all = all
+            .into_iter()
+            .map(|x| {
+                let mut shape = x.as_ref().shape().to_vec();
+                shape.insert(axis, 1);
+                x.as_ref().reshape(shape)
+            })
+            .collect();
+        Concat::concat(all, axis)
+    }
+
+    /// 沿第 `axis` 维依次切成长度为 `sizes` 的若干段
+    pub fn split(&self, sizes: &[usize], axis: usize) -> Vec<Tensor> {
+        assert_eq!(sizes.iter().sum::<usize>(), self.shape()[axis]);
+        let mut specs = vec![SliceSpec::all(); axis + 1];
+        let mut start = 0;
+        sizes
+            .iter()
+            .map(|&len| {
+                specs[axis] = SliceSpec::range(start as isize, (start + len) as isize);
+                start += len;
+                self.slice(&specs)
+            })
+            .collect()
+    }
+
+    /// 沿第 `axis` 维切成 `n` 段, 每段长度向上取整, 最后一段可能较短或缺少
+    pub fn chunk(&self, n: usize, axis: usize) -> Vec<Tensor> {
+        let dim = self.shape()[axis];
+        let len = dim.div_ceil(n).max(1);
+        let mut sizes = vec![len; dim / len];
+        if !dim.is_multiple_of(len) {
+            sizes.push(dim % len);
+        }
+        self.split(&sizes, axis)
+    }
+
+    /// 多维切片, 见 `SliceSpec`
+    pub fn slice(&self, specs: &[SliceSpec]) -> Tensor {
+        StridedSlice::slice(self.clone(), specs)
+    }
+
+    /// 在每一维前后各填充 `pads[axis]` 个元素, 见 `PadMode`
+    pub fn pad(&self, pads: &[[usize; 2]], mode: PadMode) -> Tensor {
+        Pad::tensor(self.clone(), pads.to_vec(), mode)
+    }
+
+    /// 把整个张量在第 i 维上重复 `repeats[i]` 次
+    pub fn tile(&self, repeats: &[usize]) -> Tensor {
+        Tile::tensor(self.clone(), repeats.to_vec())
+    }
+
+    /// 把第 `axis` 维的每个元素重复 `n` 次
+    pub fn repeat(&self, n: usize, axis: usize) -> Tensor {
+        let mut shape = self.shape().to_vec();
+        shape.insert(axis + 1, 1);
+        let mut repeats = vec![1; shape.len()];
+        repeats[axis + 1] = n;
+        let y = self.reshape(shape).tile(&repeats);
+        let mut shape = self.shape().to_vec();
+        shape[axis] *= n;
+        y.reshape(shape)
+    }
+
+    /// 反转 `axes` 中的各维
+    pub fn flip(&self, axes: &[usize]) -> Tensor {
+        Flip::tensor(self.clone(), axes.to_vec())
+    }
+
+    /// 沿第 `axis` 维的前缀和, 其他模式见 `Cumulative`
+    pub fn cumsum(&self, axis: usize) -> Tensor {
+        Cumulative::sum(axis).apply(self.clone())
+    }
+
+    /// 沿第 `axis` 维的前缀积
+    pub fn cumprod(&self, axis: usize) -> Tensor {
+        Cumulative::prod(axis).apply(self.clone())
+    }
+
+    /// 在 `axes` 上求平均, 结果保持原形状, 见 `GroupMean`
+    pub fn group_mean(&self, axes: &[usize]) -> Tensor {
+        GroupMean::tensor(self.clone(), axes.to_vec())
+    }
+
+    /// 在 `axes` 上减去均值并除以标准差
+    pub fn normalize(&self, axes: &[usize], eps: f32) -> Tensor {
+        Normalize::tensor(self.clone(), axes.to_vec(), eps, true)
+    }
+
+    /// 在 `axes` 上除以均方根
+    pub fn rms_normalize(&self, axes: &[usize], eps: f32) -> Tensor {
+        Normalize::tensor(self.clone(), axes.to_vec(), eps, false)
+    }
+
+    /// 第 `axis` 维上最大值的下标, 结果去掉该维; 有多个最大值时取第一个
+    pub fn argmax(&self, axis: usize) -> Tensor {
+        self.arg_first(axis, true)
+    }
+
+    /// 第 `axis` 维上最小值的下标, 结果去掉该维; 有多个最小值时取第一个
+    pub fn argmin(&self, axis: usize) -> Tensor {
+        self.arg_first(axis, false)
+    }
+
+    fn arg_first(&self, axis: usize, descending: bool) -> Tensor {
+        let mut shape = self.shape().to_vec();
+        shape.remove(axis);
+        ArgSort::tensor(self.clone(), axis, 1, descending).reshape(shape)
+    }
+
+    /// 第 `axis` 维上最大的 `k` 个值及其下标, 按从大到小排列
+    pub fn topk(&self, k: usize, axis: usize) -> (Tensor, Tensor) {
+        let indices = ArgSort::tensor(self.clone(), axis, k, true);
+        (self.gather(axis, &indices), indices)
+    }
+
+    /// 沿第 `axis` 维从小到大排序, 返回值及其原下标
+    pub fn sort(&self, axis: usize) -> (Tensor, Tensor) {
+        let indices = ArgSort::tensor(self.clone(), axis, self.shape()[axis], false);
+        (self.gather(axis, &indices), indices)
+    }
+
+    /// 见 `Gather`
+    pub fn gather<I: AsRef<Tensor>>(&self, axis: usize, indices: I) -> Tensor {
+        Gather::tensor(axis, self.clone(), indices.as_ref().clone())
+    }
+
+    /// `self` 加上把 `source` 按 `indices` 散布到第 `axis` 维的结果, 见 `ScatterAdd`
+    pub fn scatter_add<I: AsRef<Tensor>, S: AsRef<Tensor>>(
+        &self,
+        axis: usize,
+        indices: I,
+        source: S,
+    ) -> Tensor {
+        let scatter = ScatterAdd::tensor(
+            axis,
+            indices.as_ref().clone(),
+            source.as_ref().clone(),
+            self.shape().to_vec(),
+        );
+        self + scatter
+    }
+
+    /// 见 `IndexSelect`
+    pub fn index_select<I: AsRef<Tensor>>(&self, axis: usize, indices: I) -> Tensor {
+        IndexSelect::tensor(axis, self.clone(), indices.as_ref().clone())
+    }
+
+    /// `self` 加上按 `indices` 累加的 `source`, 见 `IndexAdd`
+    pub fn index_add<I: AsRef<Tensor>, S: AsRef<Tensor>>(
+        &self,
+        axis: usize,
+        indices: I,
+        source: S,
+    ) -> Tensor {
+        let add = IndexAdd::tensor(
+            axis,
+            indices.as_ref().clone(),
+            source.as_ref().clone(),
+            self.shape()[axis],
+        );
+        self + add
+    }
+
+    /// 以 `self` 为 `[vocab, dim]` 的表查找 `indices`, 见 `EmbeddingLookup`
+    pub fn embedding<I: AsRef<Tensor>>(&self, indices: I) -> Tensor {
+        EmbeddingLookup::tensor(self.clone(), indices.as_ref().clone())
+    }
+
     pub fn reshape<S: AsRef<[usize]>>(&self, shape: S) -> Tensor {
         Reshape::reshape(self.clone(), shape.as_ref().to_vec())
     }
@@ -146,13 +589,13 @@ impl Tensor {
         ExtendScale::one(shape.as_ref().to_vec())
     }
 
-    pub fn compute(&self) -> Result<Arc<Vec<f32>>, ()> {
+    pub fn compute(&self) -> CpuResult {
         self.compute_with([])
     }
     pub fn compute_with<'s, I: IntoIterator<Item = (&'s Tensor, Arc<Vec<f32>>)>>(
         &self,
         v: I,
-    ) -> Result<Arc<Vec<f32>>, ()> {
+    ) -> CpuResult {
         let mut context = CpuContext::new();
         for (var, val) in v {
             context.input(var, val);
@@ -160,7 +603,16 @@ impl Tensor {
         context.compute(self)
     }
 
-    pub fn compute_display(&self) -> Result<(), ()> {
+    /// 在 `backend` 上计算, 变量需要预先绑定
+    pub fn compute_on<B: Backend>(
+        &self,
+        backend: &mut B,
+    ) -> Result<Arc<Vec<B::Element>>, B::Error> {
+        backend.compute(self)?;
+        backend.fetch(self)
+    }
+
+    pub fn compute_display(&self) -> Result<(), CpuError> {
         let r = self.compute()?;
         let r = r.as_slice();
         match self.shape().len() {
@@ -203,16 +655,17 @@ impl Tensor {
     }
 
     pub fn back<T: AsRef<Tensor>>(&self, target: T) -> Tensor {
-        let mut context = BackwardGrad::new();
+        self.grads(&[target.as_ref().clone()]).remove(0)
+    }
+
+    /// 一次反向传播求出对各个 `targets` 的梯度, 只沿能到达目标的路径构建梯度节点
+    pub fn grads(&self, targets: &[Tensor]) -> Vec<Tensor> {
+        let mut context = BackwardGrad::new_to(targets);
         context.append(
             self,
             ExtendScale::extend(Tensor::scale(1.0), self.shape().to_vec()),
         );
-        context
-            .result()
-            .get(target.as_ref().into())
-            .unwrap()
-            .clone()
+        context.result_for(targets)
     }
 
     pub fn debug_define(&self) -> impl Debug {
@@ -285,6 +738,18 @@ impl Tensor {
         Self::new_inner(TensorInner::new(shape, arguments, operator))
     }
 
+    /// 结果类型不由参数推导的算子, 如比较和类型转换
+    pub fn new_with_dtype(
+        shape: Vec<usize>,
+        arguments: Vec<Tensor>,
+        operator: Box<dyn TensorOperator>,
+        dtype: DType,
+    ) -> Self {
+        Self::new_inner(TensorInner::new_with_dtype(
+            shape, arguments, operator, dtype,
+        ))
+    }
+
     fn new_inner(inner: TensorInner) -> Self {
         Self {
             inner: Arc::new(inner),
@@ -374,7 +839,7 @@ impl Add<f32> for Tensor {
     type Output = Tensor;
 
     fn add(self, rhs: f32) -> Self::Output {
-        Function::Add(rhs).apply(self)
+        Function::Add(rhs as f64).apply(self)
     }
 }
 
@@ -382,7 +847,7 @@ impl Add<f32> for &Tensor {
     type Output = Tensor;
 
     fn add(self, rhs: f32) -> Self::Output {
-        self.apply(Function::Add(rhs))
+        self.apply(Function::Add(rhs as f64))
     }
 }
 
@@ -430,7 +895,7 @@ impl Sub<f32> for Tensor {
     type Output = Tensor;
 
     fn sub(self, rhs: f32) -> Self::Output {
-        Function::Add(-rhs).apply(self)
+        Function::Add(-rhs as f64).apply(self)
     }
 }
 
@@ -438,7 +903,7 @@ impl Sub<f32> for &Tensor {
     type Output = Tensor;
 
     fn sub(self, rhs: f32) -> Self::Output {
-        self.apply(Function::Add(-rhs))
+        self.apply(Function::Add(-rhs as f64))
     }
 }
 
@@ -486,7 +951,7 @@ impl Mul<f32> for Tensor {
     type Output = Tensor;
 
     fn mul(self, rhs: f32) -> Self::Output {
-        Function::Mul(rhs).apply(self)
+        Function::Mul(rhs as f64).apply(self)
     }
 }
 
@@ -494,7 +959,7 @@ impl Mul<f32> for &Tensor {
     type Output = Tensor;
 
     fn mul(self, rhs: f32) -> Self::Output {
-        self.apply(Function::Mul(rhs))
+        self.apply(Function::Mul(rhs as f64))
     }
 }
 
@@ -542,7 +1007,7 @@ impl Div<f32> for Tensor {
     type Output = Tensor;
 
     fn div(self, rhs: f32) -> Self::Output {
-        Function::Mul(1.0 / rhs).apply(self)
+        Function::Mul(1.0 / rhs as f64).apply(self)
     }
 }
 
@@ -550,7 +1015,7 @@ impl Div<f32> for &Tensor {
     type Output = Tensor;
 
     fn div(self, rhs: f32) -> Self::Output {
-        self.apply(Function::Mul(1.0 / rhs))
+        self.apply(Function::Mul(1.0 / rhs as f64))
     }
 }
 
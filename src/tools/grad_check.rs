@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use crate::core::sum_scale::SumScale;
+use crate::cpu::CpuContext;
+use crate::grad::ForwardGrad;
+use crate::tensor::{data_size, Tensor};
+
+/// 各输入上解析梯度与中心差分的最大相对误差
+///
+/// 相对误差为 `|a - b| / max(|a|, |b|, 1)`, 即数值小于 1 时退化为绝对误差.
+#[derive(Debug, Clone)]
+pub struct GradCheck {
+    /// `Tensor::back` 的逐元素误差
+    pub backward: Vec<f32>,
+    /// `ForwardGrad` 在随机方向上的方向导数误差
+    pub forward: Vec<f32>,
+}
+
+impl GradCheck {
+    pub fn max_error(&self) -> f32 {
+        self.backward
+            .iter()
+            .chain(self.forward.iter())
+            .copied()
+            .fold(0.0, f32::max)
+    }
+}
+
+fn relative_error(a: f64, b: f64) -> f32 {
+    ((a - b).abs() / a.abs().max(b.abs()).max(1.0)) as f32
+}
+
+pub fn gradcheck<F: Fn(&[Tensor]) -> Tensor>(f: F, inputs: &[Tensor]) -> GradCheck {
+    gradcheck_with(f, inputs, 1e-2)
+}
+
+/// 以 `inputs` 的值为检查点, 对 `f` 的输出按固定的随机权重求和后比较梯度
+///
+/// 差分和解析梯度都在 f64 下计算, 以免舍入误差掩盖梯度错误.
+pub fn gradcheck_with<F: Fn(&[Tensor]) -> Tensor>(f: F, inputs: &[Tensor], eps: f32) -> GradCheck {
+    let rng = &mut SmallRng::seed_from_u64(0);
+    let values = inputs
+        .iter()
+        .map(|x| x.compute().unwrap().iter().map(|&x| x as f64).collect())
+        .collect::<Vec<_>>();
+    let variables = inputs
+        .iter()
+        .map(|x| Tensor::variable(x.shape()))
+        .collect::<Vec<_>>();
+
+    let output = f(&variables);
+    let weight = (0..data_size(output.shape()))
+        .map(|_| rng.gen_range(0.5..1.5))
+        .collect();
+    let loss = SumScale::sum(output.clone() * Tensor::constant(output.shape(), Arc::new(weight)));
+
+    let eval = |values: &[Vec<f64>], target: &Tensor| {
+        let mut context = CpuContext::<f64>::default();
+        for (var, val) in variables.iter().zip(values) {
+            context.input(var, Arc::new(val.clone()));
+        }
+        context.compute(target).unwrap()
+    };
+
+    let grads = loss.grads(&variables);
+
+    let eps = eps as f64;
+    let mut result = GradCheck {
+        backward: Vec::with_capacity(inputs.len()),
+        forward: Vec::with_capacity(inputs.len()),
+    };
+    for (i, var) in variables.iter().enumerate() {
+        let analytic = eval(&values, &grads[i]);
+
+        let mut numeric = Vec::with_capacity(values[i].len());
+        let mut shifted = values.clone();
+        for j in 0..values[i].len() {
+            shifted[i][j] = values[i][j] + eps;
+            let pos = eval(&shifted, &loss)[0];
+            shifted[i][j] = values[i][j] - eps;
+            let neg = eval(&shifted, &loss)[0];
+            shifted[i][j] = values[i][j];
+            numeric.push((pos - neg) / (2.0 * eps));
+        }
+
+        result.backward.push(
+            analytic
+                .iter()
+                .zip(&numeric)
+                .map(|(&a, &b)| relative_error(a, b))
+                .fold(0.0, f32::max),
+        );
+
+        let tangent = (0..numeric.len())
+            .map(|_| rng.gen_range(-1.0..1.0))
+            .collect::<Vec<f32>>();
+        let expect = numeric
+            .iter()
+            .zip(&tangent)
+            .map(|(a, &b)| a * b as f64)
+            .sum();
+        let tangent = Tensor::constant(var.shape(), Arc::new(tangent));
+        let jvp = ForwardGrad::new(&[(var.clone(), tangent)]).compute(&loss);
+        result
+            .forward
+            .push(relative_error(eval(&values, &jvp)[0], expect));
+    }
+    result
+}
+
+#[test]
+fn test() {
+    let x = Tensor::constant([3], Arc::new(vec![0.5, -1.0, 2.0]));
+    let check = gradcheck(|x| &x[0] * &x[0] * &x[0], &[x]);
+    assert!(check.max_error() < 1e-2, "{:?}", check);
+
+    // 错误的梯度应当被发现
+    let wrong = gradcheck(
+        |x| x[0].apply(crate::core::function::Function::Step),
+        &[Tensor::constant([1], Arc::new(vec![0.0]))],
+    );
+    assert!(wrong.max_error() > 0.5, "{:?}", wrong);
+}
@@ -4,9 +4,12 @@ use std::io;
 use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
 use std::ops::Deref;
 use std::path::Path;
+use std::sync::Arc;
 
 use byteorder::{ReadBytesExt, WriteBytesExt};
 
+use crate::tensor::Tensor;
+
 pub trait IdxDataType {
     type Type: Copy;
     const TYPE: u8;
@@ -340,4 +343,39 @@ impl IdxFile {
         let mut f = BufWriter::new(File::create(path)?);
         self.write(&mut f)
     }
+
+    /// 转为常量张量, f32 和 f64 的数据不做转换
+    pub fn into_tensor(self) -> Tensor {
+        let shape = self
+            .dimensions
+            .iter()
+            .map(|&x| x as usize)
+            .collect::<Vec<_>>();
+        let data = match self.data {
+            IdxData::U8 { data } => data.into_iter().map(f32::from).collect(),
+            IdxData::I8 { data } => data.into_iter().map(f32::from).collect(),
+            IdxData::I16 { data } => data.into_iter().map(f32::from).collect(),
+            IdxData::I32 { data } => data.into_iter().map(|x| x as f32).collect(),
+            IdxData::F32 { data } => data,
+            IdxData::F64 { data } => return Tensor::constant_f64(shape, Arc::new(data)),
+        };
+        Tensor::constant(shape, Arc::new(data))
+    }
+}
+
+#[test]
+fn test() {
+    use crate::cpu::CpuContext;
+
+    let file = IdxFile {
+        dimensions: vec![2],
+        data: IdxData::F64 {
+            data: vec![0.1, 0.2],
+        },
+    };
+    let mut buffer = Vec::new();
+    file.write(&mut buffer).unwrap();
+    let tensor = IdxFile::read(&mut buffer.as_slice()).unwrap().into_tensor();
+    let mut context = CpuContext::<f64>::default();
+    assert_eq!(context.compute(&tensor).unwrap().as_slice(), [0.1, 0.2]);
 }
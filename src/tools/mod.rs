@@ -1,6 +1,7 @@
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
 
+pub mod grad_check;
 pub mod idx;
 
 pub fn rand(len: usize) -> Vec<f32> {